@@ -1,7 +1,21 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::TokenInterface;
+// Achievement rewards are minted by the SPL demo through its faucet instruction,
+// so this program also depends on it with the `cpi` feature:
+//   spl_token_demo = { path = "../../day2/spl", features = ["cpi"] }
+use spl_token_demo::cpi::accounts::MintTokens;
+use spl_token_demo::program::SplTokenDemo;
+use spl_token_demo::Config as TokenConfig;
 
 declare_id!("11111111111111111111111111111111"); // Playground will replace this
 
+// Other programs can call this one by depending on it with the `cpi` feature:
+//   counter = { path = "../anchor", features = ["cpi"] }
+// Anchor then generates `counter::cpi::{increment, decrement, ...}` and
+// `counter::cpi::accounts::*`. See ../cpi/lib.rs for a caller example.
+
 #[program]
 pub mod counter {
     use super::*;
@@ -30,12 +44,199 @@ pub mod counter {
         Ok(())
     }
 
+    // Permissionless crank: reset the count once per unix day. The first call for a
+    // counter only starts tracking from today
+    pub fn reset_if_due(ctx: Context<ResetIfDue>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        let daily_reset = &mut ctx.accounts.daily_reset;
+        let today = current_day()?;
+        if daily_reset.last_reset_day == 0 {
+            daily_reset.last_reset_day = today;
+            msg!("Daily reset tracking started for day {}", today);
+            return Ok(());
+        }
+        if daily_reset.last_reset_day == today {
+            msg!("Counter already reset today, count is {}", counter.count);
+            return Ok(());
+        }
+
+        counter.count = 0;
+        daily_reset.last_reset_day = today;
+        msg!("Counter reset for day {}", today);
+        Ok(())
+    }
+
     // View the counter (this doesn't modify state but is included for completeness)
     pub fn view(ctx: Context<View>) -> Result<()> {
         let counter = &ctx.accounts.counter;
         msg!("Current count: {}", counter.count);
         Ok(())
     }
+
+    // Initialize a poll: a counter where each wallet may increment only once
+    pub fn initialize_poll(ctx: Context<InitializePoll>) -> Result<()> {
+        let poll = &mut ctx.accounts.poll;
+        poll.count = 0;
+        msg!("Poll initialized to 0");
+        Ok(())
+    }
+
+    // Cast a vote, incrementing the poll once per voter
+    pub fn vote(ctx: Context<Vote>) -> Result<()> {
+        let marker = &mut ctx.accounts.vote_marker;
+        require!(!marker.voted, CounterError::AlreadyVoted);
+        marker.voted = true;
+
+        let poll = &mut ctx.accounts.poll;
+        poll.count += 1;
+        msg!("Vote recorded from {}, poll count is {}", ctx.accounts.voter.key(), poll.count);
+        Ok(())
+    }
+
+    // Initialize an empty counter map
+    pub fn initialize_map(ctx: Context<InitializeMap>) -> Result<()> {
+        let map = &mut ctx.accounts.counter_map;
+        map.entries = Vec::new();
+        msg!("Counter map initialized");
+        Ok(())
+    }
+
+    // Add a new labelled counter starting at 0
+    pub fn insert_label(ctx: Context<UpdateMap>, label: String) -> Result<()> {
+        let map = &mut ctx.accounts.counter_map;
+        let key = CounterMap::key_for(&label);
+        require!(map.find(&key).is_none(), CounterError::LabelExists);
+        require!(map.entries.len() < CounterMap::MAX_ENTRIES, CounterError::MapFull);
+
+        map.entries.push(MapEntry { key, count: 0 });
+        msg!("Counter '{}' added ({} of {})", label, map.entries.len(), CounterMap::MAX_ENTRIES);
+        Ok(())
+    }
+
+    // Increment a labelled counter
+    pub fn increment_label(ctx: Context<UpdateMap>, label: String) -> Result<()> {
+        let map = &mut ctx.accounts.counter_map;
+        let key = CounterMap::key_for(&label);
+        let index = map.find(&key).ok_or(CounterError::LabelNotFound)?;
+
+        let entry = &mut map.entries[index];
+        entry.count += 1;
+        msg!("Counter '{}' incremented to {}", label, entry.count);
+        Ok(())
+    }
+
+    // Remove a labelled counter, freeing its slot
+    pub fn remove_label(ctx: Context<UpdateMap>, label: String) -> Result<()> {
+        let map = &mut ctx.accounts.counter_map;
+        let key = CounterMap::key_for(&label);
+        let index = map.find(&key).ok_or(CounterError::LabelNotFound)?;
+
+        // Order doesn't matter, so swap_remove avoids shifting the rest
+        map.entries.swap_remove(index);
+        msg!("Counter '{}' removed", label);
+        Ok(())
+    }
+
+    // Admin of an SPL demo mint: reward each reached achievement with `reward_amount`
+    // of that mint
+    pub fn initialize_achievement_config(
+        ctx: Context<InitializeAchievementConfig>,
+        reward_amount: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.achievement_config;
+        config.admin = ctx.accounts.admin.key();
+        config.mint = ctx.accounts.token_config.mint;
+        config.reward_amount = reward_amount;
+        config.bump = ctx.bumps.achievement_config;
+        msg!("Achievement reward set to {} of mint {}", reward_amount, config.mint);
+        Ok(())
+    }
+
+    // Admin: change the reward minted per achievement
+    pub fn set_achievement_reward(
+        ctx: Context<SetAchievementReward>,
+        reward_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.achievement_config.reward_amount = reward_amount;
+        msg!("Achievement reward set to {}", reward_amount);
+        Ok(())
+    }
+
+    // Admin: attach achievement thresholds to a counter, rewarding `authority`
+    pub fn configure_achievements(
+        ctx: Context<ConfigureAchievements>,
+        thresholds: Vec<u32>,
+    ) -> Result<()> {
+        require!(
+            !thresholds.is_empty() && thresholds.len() <= Achievements::MAX_THRESHOLDS,
+            CounterError::InvalidThresholds
+        );
+        // Thresholds must be strictly increasing so they are reached in order
+        require!(
+            thresholds.windows(2).all(|pair| pair[0] < pair[1]),
+            CounterError::InvalidThresholds
+        );
+
+        let achievements = &mut ctx.accounts.achievements;
+        achievements.counter = ctx.accounts.counter.key();
+        achievements.authority = ctx.accounts.authority.key();
+        achievements.mint = ctx.accounts.achievement_config.mint;
+        achievements.thresholds = thresholds;
+        achievements.next_index = 0;
+        msg!("Achievements configured: {:?}", achievements.thresholds);
+        Ok(())
+    }
+
+    // Authority: increment the counter and, when the next threshold is reached,
+    // mint the configured reward through the SPL demo. The mint's faucet rules
+    // (pause, caps, rate limit) apply to the reward like to any other mint_tokens call
+    pub fn increment_with_reward(ctx: Context<IncrementWithReward>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.count += 1;
+        msg!("Counter incremented to {}", counter.count);
+
+        let achievements = &mut ctx.accounts.achievements;
+        let next = achievements.next_index as usize;
+        if next >= achievements.thresholds.len() || counter.count < achievements.thresholds[next] {
+            return Ok(());
+        }
+        achievements.next_index += 1;
+
+        let reward_amount = ctx.accounts.achievement_config.reward_amount;
+        let cpi_accounts = MintTokens {
+            user: ctx.accounts.authority.to_account_info(),
+            config: ctx.accounts.token_config.to_account_info(),
+            token_mint: ctx.accounts.token_mint.to_account_info(),
+            user_token_account: ctx.accounts.authority_token_account.to_account_info(),
+            mint_record: ctx.accounts.mint_record.to_account_info(),
+            mint_stats: ctx.accounts.mint_stats.to_account_info(),
+            mint_receipt: ctx.accounts.mint_receipt.to_account_info(),
+            allowlist_entry: None,
+            gate_token_account: None,
+            referrer: None,
+            referrer_token_account: None,
+            referral_stats: None,
+            referrer_record: None,
+            mint_authority: ctx.accounts.token_mint_authority.to_account_info(),
+            memo_program: None,
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_demo_program.to_account_info(),
+            cpi_accounts,
+        );
+        spl_token_demo::cpi::mint_tokens(cpi_ctx, reward_amount, None)?;
+
+        msg!(
+            "ACHIEVEMENT_EVENT: counter={}, threshold={}, reward={}",
+            counter.key(),
+            achievements.thresholds[next],
+            reward_amount
+        );
+        Ok(())
+    }
 }
 
 // Counter account structure
@@ -44,6 +245,91 @@ pub struct Counter {
     pub count: u32,
 }
 
+// Daily reset state of a counter, kept beside it so Counter's layout is unchanged
+#[account]
+pub struct DailyReset {
+    // Unix day (timestamp / 86400) of the last reset; 0 until the first crank
+    pub last_reset_day: i64,
+}
+
+// Current unix day number from the Clock sysvar
+fn current_day() -> Result<i64> {
+    let clock = Clock::get()?;
+    Ok(clock.unix_timestamp.div_euclid(86_400))
+}
+
+// Poll account structure: like Counter, but incremented once per voter
+#[account]
+pub struct Poll {
+    pub count: u32,
+}
+
+// Marker PDA proving a voter has already voted on a poll
+#[account]
+pub struct VoteMarker {
+    pub voted: bool,
+}
+
+// Many counters in one account, keyed by the hash of their label
+#[account]
+pub struct CounterMap {
+    pub entries: Vec<MapEntry>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MapEntry {
+    pub key: [u8; 32],
+    pub count: u64,
+}
+
+impl CounterMap {
+    pub const MAX_ENTRIES: usize = 64;
+    // 8 discriminator + 4 vec length + 64 * (32 key + 8 count)
+    pub const SPACE: usize = 8 + 4 + Self::MAX_ENTRIES * (32 + 8);
+
+    // Labels are stored as fixed-size hashes so any label length fits
+    pub fn key_for(label: &str) -> [u8; 32] {
+        hash(label.as_bytes()).to_bytes()
+    }
+
+    pub fn find(&self, key: &[u8; 32]) -> Option<usize> {
+        self.entries.iter().position(|entry| &entry.key == key)
+    }
+}
+
+// Achievement settings per SPL demo mint, administered by that mint's admin
+#[account]
+pub struct AchievementConfig {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    // Tokens minted for each reached threshold
+    pub reward_amount: u64,
+    pub bump: u8,
+}
+
+impl AchievementConfig {
+    // 8 discriminator + admin + mint + reward_amount + bump
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+// Achievement thresholds attached to a counter
+#[account]
+pub struct Achievements {
+    pub counter: Pubkey,
+    pub authority: Pubkey,
+    // SPL demo mint the rewards are paid in; selects the AchievementConfig
+    pub mint: Pubkey,
+    pub thresholds: Vec<u32>,
+    // Index of the next threshold not yet reached
+    pub next_index: u8,
+}
+
+impl Achievements {
+    pub const MAX_THRESHOLDS: usize = 8;
+    // 8 discriminator + counter + authority + mint + vec (4 + 8 * 4) + next_index
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 4 + Self::MAX_THRESHOLDS * 4 + 1;
+}
+
 // Context for initializing a counter
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -61,8 +347,196 @@ pub struct Update<'info> {
     pub counter: Account<'info, Counter>,
 }
 
+// Context for the daily reset crank; anyone can call it and pays for the state
+// account on the first call
+#[derive(Accounts)]
+pub struct ResetIfDue<'info> {
+    #[account(mut)]
+    pub counter: Account<'info, Counter>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + 8,
+        seeds = [b"daily_reset", counter.key().as_ref()],
+        bump
+    )]
+    pub daily_reset: Account<'info, DailyReset>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 // Context for viewing a counter
 #[derive(Accounts)]
 pub struct View<'info> {
     pub counter: Account<'info, Counter>,
 }
+
+// Context for initializing a poll
+#[derive(Accounts)]
+pub struct InitializePoll<'info> {
+    #[account(init, payer = user, space = 8 + 4)]
+    pub poll: Account<'info, Poll>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Context for voting on a poll
+#[derive(Accounts)]
+pub struct Vote<'info> {
+    #[account(mut)]
+    pub poll: Account<'info, Poll>,
+    // One marker per (poll, voter); created on the first vote
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + 1,
+        seeds = [poll.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_marker: Account<'info, VoteMarker>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Context for initializing a counter map
+#[derive(Accounts)]
+pub struct InitializeMap<'info> {
+    #[account(init, payer = user, space = CounterMap::SPACE)]
+    pub counter_map: Account<'info, CounterMap>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Context for updating a counter map
+#[derive(Accounts)]
+pub struct UpdateMap<'info> {
+    #[account(mut)]
+    pub counter_map: Account<'info, CounterMap>,
+}
+
+// Context for creating the achievement config of an SPL demo mint
+#[derive(Accounts)]
+pub struct InitializeAchievementConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = AchievementConfig::SPACE,
+        seeds = [b"achievement_config", token_config.mint.as_ref()],
+        bump
+    )]
+    pub achievement_config: Account<'info, AchievementConfig>,
+    // The SPL demo's config for the reward mint; only its admin may set this up
+    #[account(has_one = admin @ CounterError::Unauthorized)]
+    pub token_config: Account<'info, TokenConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Context for changing the achievement reward
+#[derive(Accounts)]
+pub struct SetAchievementReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"achievement_config", achievement_config.mint.as_ref()],
+        bump = achievement_config.bump,
+        has_one = admin @ CounterError::Unauthorized
+    )]
+    pub achievement_config: Account<'info, AchievementConfig>,
+    pub admin: Signer<'info>,
+}
+
+// Context for configuring achievements on a counter
+#[derive(Accounts)]
+pub struct ConfigureAchievements<'info> {
+    pub counter: Account<'info, Counter>,
+    #[account(
+        init,
+        payer = admin,
+        space = Achievements::SPACE,
+        seeds = [b"achievements", counter.key().as_ref()],
+        bump
+    )]
+    pub achievements: Account<'info, Achievements>,
+    #[account(
+        seeds = [b"achievement_config", achievement_config.mint.as_ref()],
+        bump = achievement_config.bump,
+        has_one = admin @ CounterError::Unauthorized
+    )]
+    pub achievement_config: Account<'info, AchievementConfig>,
+    /// CHECK: Wallet that receives the rewards; stored in achievements.authority
+    pub authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Context for incrementing a counter with achievement rewards. The accounts after
+// achievement_config are forwarded to spl_token_demo::mint_tokens, which checks them
+#[derive(Accounts)]
+pub struct IncrementWithReward<'info> {
+    #[account(mut)]
+    pub counter: Account<'info, Counter>,
+    #[account(
+        mut,
+        seeds = [b"achievements", counter.key().as_ref()],
+        bump,
+        has_one = authority,
+    )]
+    pub achievements: Account<'info, Achievements>,
+    #[account(
+        seeds = [b"achievement_config", achievements.mint.as_ref()],
+        bump = achievement_config.bump,
+    )]
+    pub achievement_config: Account<'info, AchievementConfig>,
+    // Signs the mint_tokens call as its user, and pays for its accounts
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        constraint = token_config.mint == achievements.mint @ CounterError::WrongRewardMint
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    /// CHECK: The reward mint; checked against token_config by mint_tokens
+    #[account(mut, address = achievements.mint)]
+    pub token_mint: UncheckedAccount<'info>,
+    /// CHECK: Authority's ATA for the reward mint; created by mint_tokens if missing
+    #[account(mut)]
+    pub authority_token_account: UncheckedAccount<'info>,
+    /// CHECK: Authority's faucet record in the SPL demo, checked by mint_tokens
+    #[account(mut)]
+    pub mint_record: UncheckedAccount<'info>,
+    /// CHECK: The reward mint's statistics, checked by mint_tokens
+    #[account(mut)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// CHECK: Receipt created by mint_tokens, seeded by mint_stats.total_mints
+    #[account(mut)]
+    pub mint_receipt: UncheckedAccount<'info>,
+    /// CHECK: The SPL demo's mint authority PDA, checked by mint_tokens
+    pub token_mint_authority: UncheckedAccount<'info>,
+    pub token_demo_program: Program<'info, SplTokenDemo>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[error_code]
+pub enum CounterError {
+    #[msg("This wallet has already voted on this poll")]
+    AlreadyVoted,
+    #[msg("The counter map is full")]
+    MapFull,
+    #[msg("A counter with this label already exists")]
+    LabelExists,
+    #[msg("No counter with this label exists")]
+    LabelNotFound,
+    #[msg("Thresholds must be non-empty, strictly increasing, and at most 8")]
+    InvalidThresholds,
+    #[msg("Only the achievement admin can perform this action")]
+    Unauthorized,
+    #[msg("The token config does not belong to the achievement reward mint")]
+    WrongRewardMint,
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+// The counter program is pulled in as a crate with its `cpi` feature enabled:
+//   counter = { path = "../anchor", features = ["cpi"] }
+// That feature generates `counter::cpi::*` helpers and `counter::cpi::accounts::*` structs.
+use counter::cpi::accounts::Update;
+use counter::program::Counter as CounterProgram;
+use counter::Counter;
+
+declare_id!("11111111111111111111111111111111"); // Playground will replace this
+
+#[program]
+pub mod counter_caller {
+    use super::*;
+
+    // Increment a counter owned by the counter program via CPI
+    pub fn increment_counter(ctx: Context<CallCounter>) -> Result<()> {
+        let cpi_accounts = Update {
+            counter: ctx.accounts.counter.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.counter_program.to_account_info(),
+            cpi_accounts,
+        );
+
+        counter::cpi::increment(cpi_ctx)?;
+
+        // Reload to see the value written by the counter program
+        ctx.accounts.counter.reload()?;
+        msg!("Counter incremented via CPI to {}", ctx.accounts.counter.count);
+        Ok(())
+    }
+
+    // Decrement a counter owned by the counter program via CPI
+    pub fn decrement_counter(ctx: Context<CallCounter>) -> Result<()> {
+        let cpi_accounts = Update {
+            counter: ctx.accounts.counter.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.counter_program.to_account_info(),
+            cpi_accounts,
+        );
+
+        counter::cpi::decrement(cpi_ctx)?;
+
+        ctx.accounts.counter.reload()?;
+        msg!("Counter decremented via CPI to {}", ctx.accounts.counter.count);
+        Ok(())
+    }
+}
+
+// Context for calling into the counter program
+#[derive(Accounts)]
+pub struct CallCounter<'info> {
+    // Counter account owned by the counter program
+    #[account(mut)]
+    pub counter: Account<'info, Counter>,
+    // The counter program itself, checked against counter::ID
+    pub counter_program: Program<'info, CounterProgram>,
+}
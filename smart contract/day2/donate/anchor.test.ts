@@ -0,0 +1,399 @@
+// Solana Playground tests for donation_events; run with `test` after `build` and
+// `deploy`. The first run initializes the program with the Playground wallet as
+// admin, later runs reuse that state.
+import {
+  createAssociatedTokenAccountIdempotentInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+
+const LAMPORTS = web3.LAMPORTS_PER_SOL;
+
+const pda = (...seeds: (Buffer | Uint8Array)[]) =>
+  web3.PublicKey.findProgramAddressSync(seeds, pg.PROGRAM_ID)[0];
+
+const configPda = pda(Buffer.from("config"));
+const vaultPda = pda(Buffer.from("donation_vault"));
+const spacexMint = pda(Buffer.from("spacex_token_mint"));
+const badgeMint = (tier: string) =>
+  pda(Buffer.from("badge_mint"), Buffer.from(tier));
+
+// Every account record_donation takes, with the optional receipt, price and
+// referral accounts left out
+const donationAccounts = (donor: web3.PublicKey) => ({
+  donor,
+  payer: pg.wallet.publicKey,
+  vault: vaultPda,
+  donorStats: pda(Buffer.from("donor"), donor.toBuffer()),
+  globalStats: pda(Buffer.from("global_stats")),
+  leaderboard: pda(Buffer.from("leaderboard")),
+  matchingPool: pda(Buffer.from("matching_pool")),
+  spacexMint,
+  userTokenAccount: getAssociatedTokenAddressSync(spacexMint, donor, true),
+  bronzeMint: badgeMint("bronze"),
+  bronzeTokenAccount: getAssociatedTokenAddressSync(badgeMint("bronze"), donor, true),
+  silverMint: badgeMint("silver"),
+  silverTokenAccount: getAssociatedTokenAddressSync(badgeMint("silver"), donor, true),
+  goldMint: badgeMint("gold"),
+  goldTokenAccount: getAssociatedTokenAddressSync(badgeMint("gold"), donor, true),
+  mintAuthority: pda(Buffer.from("mint_authority")),
+  config: configPda,
+  priceUpdate: null,
+  receiptMint: null,
+  receiptTokenAccount: null,
+  receiptMetadata: null,
+  receiptMasterEdition: null,
+  tokenMetadataProgram: null,
+  rent: null,
+  referrer: null,
+  referrerTokenAccount: null,
+  referralStats: null,
+});
+
+// Every account donate_to_campaign takes for a native SOL donation, with
+// vesting, the wSOL and the receipt accounts left out
+const campaignDonationAccounts = async (campaign: web3.PublicKey) => {
+  const { donationCount } = await pg.program.account.campaign.fetch(campaign);
+  const rewardMint = pda(Buffer.from("reward_mint"), campaign.toBuffer());
+  return {
+    donor: pg.wallet.publicKey,
+    campaign,
+    campaignVault: pda(Buffer.from("vault"), campaign.toBuffer()),
+    feeVault: pda(Buffer.from("fee_vault")),
+    contribution: pda(
+      Buffer.from("contribution"),
+      campaign.toBuffer(),
+      pg.wallet.publicKey.toBuffer()
+    ),
+    donationRecord: pda(
+      Buffer.from("donation_record"),
+      campaign.toBuffer(),
+      new BN(donationCount).toArrayLike(Buffer, "le", 8)
+    ),
+    rewardMint,
+    userTokenAccount: getAssociatedTokenAddressSync(rewardMint, pg.wallet.publicKey),
+    mintAuthority: pda(Buffer.from("mint_authority")),
+    config: configPda,
+    priceUpdate: null,
+    rewardVesting: null,
+    vestingVault: null,
+    wsolAccount: null,
+    nativeMint: null,
+    wsolTemp: null,
+    receiptMint: null,
+    receiptTokenAccount: null,
+    receiptMetadata: null,
+    receiptMasterEdition: null,
+    tokenMetadataProgram: null,
+    rent: null,
+  };
+};
+
+// Creates a campaign for the Playground wallet that is already accepting
+// donations, with the wallet as beneficiary
+const createCampaign = async (goal: number) => {
+  const name = `test-${Date.now()}`;
+  const campaign = pda(
+    Buffer.from("campaign"),
+    pg.wallet.publicKey.toBuffer(),
+    Buffer.from(name)
+  );
+  const now = Math.floor(Date.now() / 1000);
+  await pg.program.methods
+    .createCampaign(
+      name,
+      new BN(goal),
+      new BN(now - 60),
+      new BN(now + 3600),
+      pg.wallet.publicKey,
+      new BN(0),
+      false
+    )
+    .accounts({
+      creator: pg.wallet.publicKey,
+      campaign,
+      campaignVault: pda(Buffer.from("vault"), campaign.toBuffer()),
+      verifiedBeneficiary: null,
+      rewardMint: pda(Buffer.from("reward_mint"), campaign.toBuffer()),
+      mintAuthority: pda(Buffer.from("mint_authority")),
+    })
+    .rpc();
+  return campaign;
+};
+
+const expectError = async (tx: Promise<unknown>, code: string) => {
+  try {
+    await tx;
+  } catch (err) {
+    assert.include(err.toString(), code);
+    return;
+  }
+  assert.fail(`expected ${code}`);
+};
+
+const sendLamports = (to: web3.PublicKey, lamports: number) =>
+  pg.program.provider.sendAndConfirm(
+    new web3.Transaction().add(
+      web3.SystemProgram.transfer({
+        fromPubkey: pg.wallet.publicKey,
+        toPubkey: to,
+        lamports,
+      })
+    )
+  );
+
+// Compute units a confirmed transaction consumed, as reported by the validator
+const computeUnits = async (signature: string) => {
+  await pg.connection.confirmTransaction(signature, "confirmed");
+  const tx = await pg.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  return tx.meta.computeUnitsConsumed;
+};
+
+// Creates `owner`'s reward ATA in a transaction of its own and returns the
+// compute units the ATA program spent on it
+const createAtaDirectly = async (mint: web3.PublicKey, owner: web3.PublicKey) =>
+  computeUnits(
+    await pg.program.provider.sendAndConfirm(
+      new web3.Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(
+          pg.wallet.publicKey,
+          getAssociatedTokenAddressSync(mint, owner, true),
+          owner,
+          mint
+        )
+      )
+    )
+  );
+
+// Allowance for invoking the ATA program from inside an instruction rather than
+// from the transaction: the CPI itself and passing its accounts
+const CPI_OVERHEAD_CU = 5_000;
+
+describe("donation_events", () => {
+  before(async () => {
+    const config = await pg.connection.getAccountInfo(configPda);
+    if (!config) {
+      await pg.program.methods.initialize().accounts({ admin: pg.wallet.publicKey }).rpc();
+    }
+  });
+
+  // System-owned PDA donors are covered by day2/donate_cpi, which signs for
+  // its treasury PDA through invoke_signed
+  it("rejects a donor the system program cannot debit", async () => {
+    const donor = new web3.Keypair();
+    const funded = 0.05 * LAMPORTS;
+    await pg.program.provider.sendAndConfirm(
+      new web3.Transaction().add(
+        web3.SystemProgram.createAccount({
+          fromPubkey: pg.wallet.publicKey,
+          newAccountPubkey: donor.publicKey,
+          lamports: funded,
+          space: 0,
+          programId: pg.PROGRAM_ID,
+        })
+      ),
+      [donor]
+    );
+
+    await expectError(
+      pg.program.methods
+        .recordDonation(new BN(0.01 * LAMPORTS), false)
+        .accounts(donationAccounts(donor.publicKey))
+        .signers([donor])
+        .rpc(),
+      "UnsupportedDonorAccount"
+    );
+    assert.equal(await pg.connection.getBalance(donor.publicKey), funded);
+  });
+
+  describe("vault drains", () => {
+    // Every vault is a data-less system account, so this is the reserve each
+    // payout path has to leave behind
+    let reserve: number;
+
+    before(async () => {
+      reserve = await pg.connection.getMinimumBalanceForRentExemption(0);
+    });
+
+    it("execute_withdrawal cannot empty the donation vault", async () => {
+      // The wallet becomes the sole approver; once approvers exist, replacing
+      // them needs the current ones to sign as remaining accounts
+      await pg.program.methods
+        .setApprovers([pg.wallet.publicKey], 1)
+        .accounts({ admin: pg.wallet.publicKey, config: configPda })
+        .remainingAccounts([
+          { pubkey: pg.wallet.publicKey, isSigner: true, isWritable: false },
+        ])
+        .rpc();
+
+      const propose = async (amount: number) => {
+        const { proposalCount } = await pg.program.account.config.fetch(configPda);
+        const proposal = pda(
+          Buffer.from("proposal"),
+          new BN(proposalCount).toArrayLike(Buffer, "le", 8)
+        );
+        await pg.program.methods
+          .proposeWithdrawal(new BN(amount), pg.wallet.publicKey)
+          .accounts({ proposer: pg.wallet.publicKey, config: configPda, proposal })
+          .rpc();
+        return pg.program.methods.executeWithdrawal().accounts({
+          config: configPda,
+          proposal,
+          vault: vaultPda,
+          destination: pg.wallet.publicKey,
+        });
+      };
+
+      await sendLamports(vaultPda, 0.01 * LAMPORTS);
+      const balance = await pg.connection.getBalance(vaultPda);
+      await expectError((await propose(balance)).rpc(), "WouldBreachRentExemption");
+      await expectError((await propose(balance + 1)).rpc(), "InsufficientVaultBalance");
+
+      await (await propose(balance - reserve)).rpc();
+      assert.equal(await pg.connection.getBalance(vaultPda), reserve);
+    });
+
+    it("withdraw_fees cannot empty the fee vault", async () => {
+      const feeVault = pda(Buffer.from("fee_vault"));
+      const withdraw = (amount: number) =>
+        pg.program.methods
+          .withdrawFees(new BN(amount))
+          .accounts({ admin: pg.wallet.publicKey, config: configPda, feeVault })
+          .rpc();
+
+      await sendLamports(feeVault, 0.01 * LAMPORTS);
+      const balance = await pg.connection.getBalance(feeVault);
+      await expectError(withdraw(balance), "WouldBreachRentExemption");
+      await expectError(withdraw(balance + 1), "InsufficientVaultBalance");
+
+      await withdraw(balance - reserve);
+      assert.equal(await pg.connection.getBalance(feeVault), reserve);
+    });
+
+    it("claim_funds leaves the campaign vault its rent reserve", async () => {
+      const goal = 0.01 * LAMPORTS;
+      const campaign = await createCampaign(goal);
+      const campaignVault = pda(Buffer.from("vault"), campaign.toBuffer());
+      // Donate at least the goal even with a platform fee taken off
+      await pg.program.methods
+        .donateToCampaign(new BN(2 * goal), "")
+        .accounts(await campaignDonationAccounts(campaign))
+        .rpc();
+      // Lamports sent straight to the vault are claimable too, but never the reserve
+      await sendLamports(campaignVault, 0.005 * LAMPORTS);
+
+      await pg.program.methods
+        .claimFunds()
+        .accounts({
+          beneficiary: pg.wallet.publicKey,
+          campaign,
+          campaignVault,
+          verifiedBeneficiary: null,
+        })
+        .rpc();
+      assert.equal(await pg.connection.getBalance(campaignVault), reserve);
+
+      await expectError(
+        pg.program.methods
+          .claimFunds()
+          .accounts({
+            beneficiary: pg.wallet.publicKey,
+            campaign,
+            campaignVault,
+            verifiedBeneficiary: null,
+          })
+          .rpc(),
+        "AlreadyClaimed"
+      );
+    });
+  });
+
+  it("refunds close the donation window and lower the donor's contribution", async () => {
+    const campaign = await createCampaign(10 * LAMPORTS);
+    const accounts = await campaignDonationAccounts(campaign);
+    const amount = 0.02 * LAMPORTS;
+    await pg.program.methods
+      .donateToCampaign(new BN(amount), "")
+      .accounts(accounts)
+      .rpc();
+
+    await pg.program.methods
+      .cancelCampaign()
+      .accounts({ creator: pg.wallet.publicKey, campaign })
+      .rpc();
+    assert.isTrue((await pg.program.account.campaign.fetch(campaign)).windowClosed);
+
+    const record = await pg.program.account.donationRecord.fetch(accounts.donationRecord);
+    await pg.program.methods
+      .refund()
+      .accounts({
+        donor: pg.wallet.publicKey,
+        campaign,
+        campaignVault: accounts.campaignVault,
+        donationRecord: accounts.donationRecord,
+        contribution: accounts.contribution,
+      })
+      .rpc();
+
+    // Only the net amount comes back, so the platform fee still counts
+    const { totalDonated } = await pg.program.account.contribution.fetch(
+      accounts.contribution
+    );
+    assert.equal(totalDonated.toNumber(), amount - record.lamports.toNumber());
+  });
+
+  // Each donation path is measured twice, once creating the reward ATA in the
+  // instruction and once with it created beforehand. With create_idempotent the
+  // difference is only the ATA program's own work; the init_if_needed
+  // constraint it replaced also deserialized and re-validated the new account
+  describe("reward ATA creation cost", () => {
+    it("record_donation adds no more than the ATA program's own cost", async () => {
+      // Fresh donors, so both runs also create the same donor profile
+      const donate = async (donor: web3.Keypair) =>
+        computeUnits(
+          await pg.program.methods
+            .recordDonation(new BN(0.01 * LAMPORTS), false)
+            .accounts(donationAccounts(donor.publicKey))
+            .signers([donor])
+            .rpc()
+        );
+
+      const creatingDonor = new web3.Keypair();
+      await sendLamports(creatingDonor.publicKey, 0.05 * LAMPORTS);
+      const creating = await donate(creatingDonor);
+
+      const existingDonor = new web3.Keypair();
+      await sendLamports(existingDonor.publicKey, 0.05 * LAMPORTS);
+      const ataCost = await createAtaDirectly(spacexMint, existingDonor.publicKey);
+      const existing = await donate(existingDonor);
+
+      assert.isAbove(creating, existing);
+      assert.isAtMost(creating - existing, ataCost + CPI_OVERHEAD_CU);
+    });
+
+    it("donate_to_campaign adds no more than the ATA program's own cost", async () => {
+      // A new campaign each, so both runs are the wallet's first donation to it
+      const donate = async (campaign: web3.PublicKey) =>
+        computeUnits(
+          await pg.program.methods
+            .donateToCampaign(new BN(0.01 * LAMPORTS), "")
+            .accounts(await campaignDonationAccounts(campaign))
+            .rpc()
+        );
+
+      const creating = await donate(await createCampaign(LAMPORTS));
+
+      const campaign = await createCampaign(LAMPORTS);
+      const ataCost = await createAtaDirectly(
+        pda(Buffer.from("reward_mint"), campaign.toBuffer()),
+        pg.wallet.publicKey
+      );
+      const existing = await donate(campaign);
+
+      assert.isAbove(creating, existing);
+      assert.isAtMost(creating - existing, ataCost + CPI_OVERHEAD_CU);
+    });
+  });
+});
@@ -1,5 +1,24 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{Transfer, transfer, System};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::associated_token::{
+    self, get_associated_token_address, AssociatedToken, Create,
+};
+use anchor_spl::metadata::{
+    create_master_edition_v3, create_metadata_accounts_v3, mpl_token_metadata::types::DataV2,
+    CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata,
+};
+use anchor_spl::token::{
+    self, spl_token::native_mint, Burn, CloseAccount, Mint, MintTo, Token, TokenAccount,
+};
+use anchor_spl::token_interface::{
+    self, Mint as PaymentMint, TokenAccount as PaymentTokenAccount, TokenInterface,
+    TransferChecked,
+};
+// Besides anchor-lang and anchor-spl, USD pricing needs pyth-solana-receiver-sdk
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
 declare_id!("HPHXtE7dhKP8R1iANQeTZiSFpYcpzmqjBz1CTTunfj4K");
 
@@ -7,8 +26,410 @@ declare_id!("HPHXtE7dhKP8R1iANQeTZiSFpYcpzmqjBz1CTTunfj4K");
 pub mod donation_events {
     use super::*;
 
-    pub fn record_donation(ctx: Context<RecordDonation>, amount: u64) -> Result<()> {
-        // Transfer SOL from donor to vault
+    // One-time setup: create the config, the SpaceX reward mint, and a rent-exempt
+    // donation vault. The caller becomes the admin; vault withdrawals additionally
+    // need the approvers configured with set_approvers
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.fee_bps = 0;
+        config.reward_tiers = Vec::new();
+        // 1 token per 0.001 SOL until the admin changes it
+        config.rate_numerator = 1;
+        config.rate_denominator = 1_000_000;
+        config.min_donation_lamports = 1_000_000;
+        config.payment_mint = Pubkey::default();
+        config.payment_rate_numerator = 0;
+        config.payment_rate_denominator = 1;
+        config.receipt_threshold_lamports = 0;
+        config.receipt_uri = String::new();
+        config.paused = false;
+        config.badge_thresholds = [0; 3];
+        config.approvers = Vec::new();
+        config.approval_threshold = 0;
+        config.proposal_count = 0;
+        config.early_bird_multiplier_bps = 0;
+        config.early_bird_goal_bps = 0;
+        config.early_bird_seconds = 0;
+        config.vesting_cliff_seconds = 0;
+        config.vesting_duration_seconds = 0;
+        config.referral_bps = 0;
+        config.sol_usd_feed_id = [0; 32];
+        config.tokens_per_usd = 0;
+        config.max_price_age_seconds = 0;
+        config.max_price_confidence_bps = 0;
+        config.streak_bonus_bps_per_day = 0;
+        config.max_streak_bonus_bps = 0;
+        config.bump = ctx.bumps.config;
+
+        let matching_pool = &mut ctx.accounts.matching_pool;
+        matching_pool.total_funded = 0;
+        matching_pool.total_matched = 0;
+        matching_pool.bump = ctx.bumps.matching_pool;
+
+        // Fund both vaults up to rent exemption so withdrawals can always leave them
+        // open and the first fee transfer is not rejected for a below-rent balance
+        let rent = Rent::get()?;
+        for vault in [
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.fee_vault.to_account_info(),
+        ] {
+            let vault_rent = rent.minimum_balance(0).saturating_sub(vault.lamports());
+            if vault_rent > 0 {
+                transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.admin.to_account_info(),
+                            to: vault,
+                        },
+                    ),
+                    vault_rent,
+                )?;
+            }
+        }
+
+        msg!(
+            "Donation program initialized, admin={}, spacex_mint={}",
+            ctx.accounts.config.admin,
+            ctx.accounts.spacex_mint.key()
+        );
+        Ok(())
+    }
+
+    // Start a fundraiser collecting into its own vault from `start_ts` until `end_ts`,
+    // with its own reward mint for donors.
+    // Each donor may give at most `max_per_donor` lamports in total; 0 means no cap.
+    // With `require_verified`, the beneficiary must be in the verified registry both
+    // now and when claiming
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_campaign(
+        ctx: Context<CreateCampaign>,
+        name: String,
+        goal_lamports: u64,
+        start_ts: i64,
+        end_ts: i64,
+        beneficiary: Pubkey,
+        max_per_donor: u64,
+        require_verified: bool,
+    ) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= MAX_CAMPAIGN_NAME_LEN,
+            DonationError::InvalidCampaignName
+        );
+        require!(goal_lamports > 0, DonationError::InvalidGoal);
+        require!(
+            !require_verified || ctx.accounts.verified_beneficiary.is_some(),
+            DonationError::BeneficiaryNotVerified
+        );
+        require!(
+            end_ts > start_ts && end_ts > Clock::get()?.unix_timestamp,
+            DonationError::InvalidDeadline
+        );
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.creator = ctx.accounts.creator.key();
+        campaign.beneficiary = beneficiary;
+        campaign.name = name;
+        campaign.goal_lamports = goal_lamports;
+        campaign.start_ts = start_ts;
+        campaign.end_ts = end_ts;
+        campaign.raised = 0;
+        campaign.donation_count = 0;
+        campaign.claimed = false;
+        campaign.max_per_donor = max_per_donor;
+        campaign.first_donation_ts = 0;
+        campaign.last_donation_ts = 0;
+        campaign.cancelled = false;
+        // Set afterwards with update_campaign_metadata
+        campaign.description_uri = String::new();
+        campaign.image_uri = String::new();
+        campaign.update_count = 0;
+        campaign.require_verified = require_verified;
+        campaign.window_closed = false;
+        campaign.bump = ctx.bumps.campaign;
+
+        // The creator funds the escrow vault's rent reserve, so refunds and the
+        // claim can pay out every donated lamport without closing it
+        let vault_rent = Rent::get()?
+            .minimum_balance(0)
+            .saturating_sub(ctx.accounts.campaign_vault.lamports());
+        if vault_rent > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: ctx.accounts.campaign_vault.to_account_info(),
+                    },
+                ),
+                vault_rent,
+            )?;
+        }
+
+        msg!(
+            "CAMPAIGN_CREATED_EVENT: campaign={}, name={}, goal={}, start={}, end={}, beneficiary={}, reward_mint={}",
+            campaign.key(),
+            campaign.name,
+            goal_lamports,
+            start_ts,
+            end_ts,
+            beneficiary,
+            ctx.accounts.reward_mint.key()
+        );
+
+        Ok(())
+    }
+
+    // Admin: set the platform fee split off each campaign donation, in basis points
+    pub fn set_fee(ctx: Context<UpdateConfig>, bps: u16) -> Result<()> {
+        require!(bps <= MAX_FEE_BPS, DonationError::InvalidFee);
+        ctx.accounts.config.fee_bps = bps;
+        msg!("Platform fee set to {} bps", bps);
+        Ok(())
+    }
+
+    // Admin: stop accepting donations, e.g. while an incident is investigated
+    pub fn pause(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.config.paused = true;
+        msg!("Donations paused");
+        Ok(())
+    }
+
+    // Admin: accept donations again
+    pub fn unpause(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.config.paused = false;
+        msg!("Donations unpaused");
+        Ok(())
+    }
+
+    // Admin: set the base reward to `numerator / denominator` tokens per lamport
+    pub fn set_rate(ctx: Context<UpdateConfig>, numerator: u64, denominator: u64) -> Result<()> {
+        require!(numerator > 0 && denominator > 0, DonationError::InvalidRate);
+        let config = &mut ctx.accounts.config;
+        config.rate_numerator = numerator;
+        config.rate_denominator = denominator;
+        msg!("Reward rate set to {}/{} tokens per lamport", numerator, denominator);
+        Ok(())
+    }
+
+    // Admin: set the smallest donation accepted, in lamports
+    pub fn set_min_donation(ctx: Context<UpdateConfig>, min_donation_lamports: u64) -> Result<()> {
+        ctx.accounts.config.min_donation_lamports = min_donation_lamports;
+        msg!("Minimum donation set to {} lamports", min_donation_lamports);
+        Ok(())
+    }
+
+    // Admin: accept `payment_mint` (e.g. devnet USDC) in record_spl_donation, rewarding
+    // `numerator / denominator` SpaceX tokens per base unit
+    pub fn set_payment_mint(
+        ctx: Context<SetPaymentMint>,
+        numerator: u64,
+        denominator: u64,
+    ) -> Result<()> {
+        require!(numerator > 0 && denominator > 0, DonationError::InvalidRate);
+        let config = &mut ctx.accounts.config;
+        config.payment_mint = ctx.accounts.payment_mint.key();
+        config.payment_rate_numerator = numerator;
+        config.payment_rate_denominator = denominator;
+
+        msg!(
+            "Payment mint set to {} at {}/{} tokens per unit",
+            config.payment_mint,
+            numerator,
+            denominator
+        );
+        Ok(())
+    }
+
+    // Admin: donations of at least `threshold_lamports` mint a receipt NFT whose
+    // metadata uri is `uri` plus the amount and campaign; 0 disables receipts
+    pub fn set_receipt_config(
+        ctx: Context<UpdateConfig>,
+        threshold_lamports: u64,
+        uri: String,
+    ) -> Result<()> {
+        require!(uri.len() <= MAX_RECEIPT_URI_LEN, DonationError::ReceiptUriTooLong);
+        let config = &mut ctx.accounts.config;
+        config.receipt_threshold_lamports = threshold_lamports;
+        config.receipt_uri = uri;
+        msg!("Receipt NFTs enabled from {} lamports", threshold_lamports);
+        Ok(())
+    }
+
+    // Admin: lifetime totals at which donors earn the bronze, silver and gold badges.
+    // A 0 disables that badge; the others must increase from bronze to gold
+    pub fn set_badge_thresholds(ctx: Context<UpdateConfig>, thresholds: [u64; 3]) -> Result<()> {
+        let enabled: Vec<u64> = thresholds.iter().copied().filter(|&t| t > 0).collect();
+        require!(
+            enabled.windows(2).all(|pair| pair[0] < pair[1]),
+            DonationError::InvalidBadgeThresholds
+        );
+        ctx.accounts.config.badge_thresholds = thresholds;
+        msg!(
+            "Badge thresholds set to bronze={}, silver={}, gold={}",
+            thresholds[0],
+            thresholds[1],
+            thresholds[2]
+        );
+        Ok(())
+    }
+
+    // Admin: campaign donations earn `multiplier_bps / 10000` times the reward while
+    // the campaign has raised less than `goal_bps` of its goal, or within `seconds`
+    // of its start. A multiplier of 0 disables the bonus, as does a 0 for either
+    // condition on its own
+    pub fn set_early_bird(
+        ctx: Context<UpdateConfig>,
+        multiplier_bps: u16,
+        goal_bps: u16,
+        seconds: i64,
+    ) -> Result<()> {
+        require!(
+            (multiplier_bps == 0 || multiplier_bps >= 10_000)
+                && goal_bps <= 10_000
+                && seconds >= 0,
+            DonationError::InvalidEarlyBird
+        );
+        let config = &mut ctx.accounts.config;
+        config.early_bird_multiplier_bps = multiplier_bps;
+        config.early_bird_goal_bps = goal_bps;
+        config.early_bird_seconds = seconds;
+        msg!(
+            "Early-bird bonus set to {} bps below {} bps of goal or within {}s of start",
+            multiplier_bps,
+            goal_bps,
+            seconds
+        );
+        Ok(())
+    }
+
+    // Admin: vest campaign donation rewards linearly over `duration_seconds`, with
+    // nothing claimable for the first `cliff_seconds`; a duration of 0 mints
+    // rewards straight to the donor again
+    pub fn set_vesting(
+        ctx: Context<UpdateConfig>,
+        cliff_seconds: i64,
+        duration_seconds: i64,
+    ) -> Result<()> {
+        require!(
+            duration_seconds >= 0 && cliff_seconds >= 0 && cliff_seconds <= duration_seconds,
+            DonationError::InvalidVesting
+        );
+        let config = &mut ctx.accounts.config;
+        config.vesting_cliff_seconds = cliff_seconds;
+        config.vesting_duration_seconds = duration_seconds;
+        msg!(
+            "Reward vesting set to a {}s cliff over {}s",
+            cliff_seconds,
+            duration_seconds
+        );
+        Ok(())
+    }
+
+    // Admin: referrers of a record_donation donation receive `bps` of the donor's
+    // reward tokens on top, at most MAX_REFERRAL_BPS; 0 turns referrals off
+    pub fn set_referral_bps(ctx: Context<UpdateConfig>, bps: u16) -> Result<()> {
+        require!(bps <= MAX_REFERRAL_BPS, DonationError::InvalidReferralBps);
+        ctx.accounts.config.referral_bps = bps;
+        msg!("Referral reward set to {} bps", bps);
+        Ok(())
+    }
+
+    // Admin: reward `tokens_per_usd` per dollar donated, priced by the Pyth SOL/USD
+    // feed `feed_id`, instead of the fixed lamport rate. Prices older than
+    // `max_age_seconds` or less certain than `max_confidence_bps` are rejected, and
+    // a `tokens_per_usd` of 0 goes back to the lamport rate
+    pub fn set_usd_pricing(
+        ctx: Context<UpdateConfig>,
+        feed_id: [u8; 32],
+        tokens_per_usd: u64,
+        max_age_seconds: u64,
+        max_confidence_bps: u16,
+    ) -> Result<()> {
+        require!(
+            tokens_per_usd == 0 || (max_age_seconds > 0 && max_confidence_bps <= 10_000),
+            DonationError::InvalidUsdPricing
+        );
+        let config = &mut ctx.accounts.config;
+        config.sol_usd_feed_id = feed_id;
+        config.tokens_per_usd = tokens_per_usd;
+        config.max_price_age_seconds = max_age_seconds;
+        config.max_price_confidence_bps = max_confidence_bps;
+        msg!(
+            "USD pricing set to {} tokens per USD, max age {}s, max confidence {} bps",
+            tokens_per_usd,
+            max_age_seconds,
+            max_confidence_bps
+        );
+        Ok(())
+    }
+
+    // Admin: record_donation rewards grow by `bps_per_day` for every consecutive day
+    // of a donor's streak after the first, up to `max_bonus_bps`
+    pub fn set_streak_bonus(
+        ctx: Context<UpdateConfig>,
+        bps_per_day: u16,
+        max_bonus_bps: u16,
+    ) -> Result<()> {
+        require!(max_bonus_bps <= 10_000, DonationError::InvalidStreakBonus);
+        let config = &mut ctx.accounts.config;
+        config.streak_bonus_bps_per_day = bps_per_day;
+        config.max_streak_bonus_bps = max_bonus_bps;
+        msg!(
+            "Streak bonus set to {} bps per day, up to {} bps",
+            bps_per_day,
+            max_bonus_bps
+        );
+        Ok(())
+    }
+
+    // Admin: replace the reward tiers; each must raise both the threshold and the
+    // multiplier over the previous one
+    pub fn set_reward_tiers(ctx: Context<UpdateConfig>, tiers: Vec<RewardTier>) -> Result<()> {
+        require!(tiers.len() <= MAX_REWARD_TIERS, DonationError::InvalidRewardTiers);
+        require!(
+            tiers.windows(2).all(|pair| {
+                pair[0].min_lamports < pair[1].min_lamports
+                    && pair[0].multiplier_bps < pair[1].multiplier_bps
+            }),
+            DonationError::InvalidRewardTiers
+        );
+        require!(
+            tiers.iter().all(|tier| tier.multiplier_bps >= 10_000),
+            DonationError::InvalidRewardTiers
+        );
+
+        msg!("Reward tiers updated: {} tiers", tiers.len());
+        ctx.accounts.config.reward_tiers = tiers;
+        Ok(())
+    }
+
+    // Donate to the global vault. An anonymous donation still updates the donor's
+    // stats, but its events carry a zeroed donor and it stays off the leaderboard.
+    // The donor may be a PDA signed by its program through invoke_signed, e.g. a
+    // Squads vault; `payer` covers account rent so the donor only spends `amount`.
+    // Returns a DonationReceipt through return data, so a simulation or the
+    // transaction response confirms the result without fetching accounts
+    pub fn record_donation(
+        ctx: Context<RecordDonation>,
+        amount: u64,
+        anonymous: bool,
+    ) -> Result<DonationReceipt> {
+        validate_donation(&ctx.accounts.config, amount)?;
+        let public_donor = if anonymous {
+            Pubkey::default()
+        } else {
+            ctx.accounts.donor.key()
+        };
+
+        // Transfer SOL from donor to vault. The system program only debits accounts
+        // it owns that carry no data: keypair wallets and system-owned PDAs such as
+        // a Squads vault, which sign through invoke_signed from their program
+        require!(
+            ctx.accounts.donor.owner == &System::id() && ctx.accounts.donor.data_is_empty(),
+            DonationError::UnsupportedDonorAccount
+        );
         let transfer_instruction = Transfer {
             from: ctx.accounts.donor.to_account_info(),
             to: ctx.accounts.vault.to_account_info(),
@@ -21,30 +442,3382 @@ pub mod donation_events {
             ),
             amount,
         )?;
-        
+
+        create_reward_account(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.payer,
+            &ctx.accounts.donor,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.spacex_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program,
+        )?;
+        let sol_usd = sol_usd_price(&ctx.accounts.config, ctx.accounts.price_update.as_ref())?;
+        let base_tokens = reward_for_lamports(&ctx.accounts.config, sol_usd, amount)?;
+
+        // Consecutive-day streaks earn a growing bonus on top of the base reward
         let clock = Clock::get()?;
-        
+        let today = clock.unix_timestamp.div_euclid(SECONDS_PER_DAY);
+        let streak_days = ctx.accounts.donor_stats.streak_after(today);
+        let config = &ctx.accounts.config;
+        let streak_bonus_bps = ((streak_days - 1) as u64)
+            .saturating_mul(config.streak_bonus_bps_per_day as u64)
+            .min(config.max_streak_bonus_bps as u64);
+        let bonus_tokens = (base_tokens as u128)
+            .checked_mul(streak_bonus_bps as u128)
+            .map(|value| value / 10_000)
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(DonationError::MathOverflow)?;
+        let tokens_minted = base_tokens
+            .checked_add(bonus_tokens)
+            .ok_or(DonationError::MathOverflow)?;
+
+        // Sponsors match the donation 1:1 while the pool has funds above its rent
+        let matching_pool = &mut ctx.accounts.matching_pool;
+        let pool_info = matching_pool.to_account_info();
+        let pool_reserve = Rent::get()?.minimum_balance(pool_info.data_len());
+        let matched_lamports = amount.min(pool_info.lamports().saturating_sub(pool_reserve));
+        let matched_tokens = reward_amount(&ctx.accounts.config, sol_usd, matched_lamports)?;
+        if matched_tokens > 0 {
+            // The pool is owned by this program, so lamports move directly
+            **pool_info.try_borrow_mut_lamports()? = pool_info
+                .lamports()
+                .checked_sub(matched_lamports)
+                .ok_or(DonationError::MathOverflow)?;
+            let vault_info = ctx.accounts.vault.to_account_info();
+            **vault_info.try_borrow_mut_lamports()? = vault_info
+                .lamports()
+                .checked_add(matched_lamports)
+                .ok_or(DonationError::MathOverflow)?;
+            matching_pool.total_matched = matching_pool.total_matched
+                .checked_add(matched_lamports)
+                .ok_or(DonationError::MathOverflow)?;
+        }
+
+        mint_reward(
+            &ctx.accounts.token_program,
+            &ctx.accounts.spacex_mint,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            tokens_minted
+                .checked_add(matched_tokens)
+                .ok_or(DonationError::MathOverflow)?,
+        )?;
+
+        if matched_tokens > 0 {
+            emit!(DonationMatched {
+                donor: public_donor,
+                matched_lamports,
+                tokens_minted: matched_tokens,
+                pool_remaining: pool_info.lamports().saturating_sub(pool_reserve),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        let donation_index = record_lifetime_totals(
+            &mut ctx.accounts.donor_stats,
+            &mut ctx.accounts.global_stats,
+            ctx.accounts.donor.key(),
+            amount,
+            clock.unix_timestamp,
+        )?;
+        if !anonymous {
+            update_leaderboard(
+                &mut ctx.accounts.leaderboard,
+                ctx.accounts.donor.key(),
+                ctx.accounts.donor_stats.total_donated,
+            );
+        }
+
+        let accounts = &mut ctx.accounts;
+        award_badges(
+            &accounts.config,
+            &mut accounts.donor_stats,
+            &accounts.donor.to_account_info(),
+            &accounts.payer,
+            [
+                (&*accounts.bronze_mint, &accounts.bronze_token_account),
+                (&*accounts.silver_mint, &accounts.silver_token_account),
+                (&*accounts.gold_mint, &accounts.gold_token_account),
+            ],
+            &accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            (&accounts.associated_token_program, &accounts.system_program, &accounts.token_program),
+        )?;
+
+        if ctx.accounts.referrer.is_some() {
+            ctx.accounts.reward_referrer(
+                ctx.bumps.mint_authority,
+                ctx.bumps.referral_stats,
+                amount,
+                tokens_minted,
+                public_donor,
+            )?;
+        }
+
+        // Large donations also earn a one-of-one receipt NFT
+        let threshold = ctx.accounts.config.receipt_threshold_lamports;
+        if threshold > 0 && amount >= threshold {
+            let accounts = &ctx.accounts;
+            mint_receipt_nft(
+                &accounts.config,
+                (
+                    accounts.receipt_mint.as_ref(),
+                    accounts.receipt_token_account.as_ref(),
+                    accounts.receipt_metadata.as_ref(),
+                    accounts.receipt_master_edition.as_ref(),
+                    accounts.token_metadata_program.as_ref(),
+                    accounts.rent.as_ref(),
+                ),
+                &accounts.donor,
+                &accounts.payer,
+                &accounts.mint_authority,
+                ctx.bumps.mint_authority,
+                (&accounts.associated_token_program, &accounts.system_program, &accounts.token_program),
+                amount,
+                // The global vault is not a campaign
+                Pubkey::default(),
+                public_donor,
+            )?;
+        }
+
+        emit!(DonationReceived {
+            donor: public_donor,
+            lamports: amount,
+            // The global vault is not a campaign, so it pays no platform fee
+            fee_lamports: 0,
+            net_lamports: amount,
+            tokens_minted,
+            bonus_tokens,
+            campaign: Pubkey::default(),
+            paid_in_wsol: false,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(DonationReceipt {
+            donation_index,
+            tokens_minted: tokens_minted
+                .checked_add(matched_tokens)
+                .ok_or(DonationError::MathOverflow)?,
+            donor_total: ctx.accounts.donor_stats.total_donated,
+        })
+    }
+
+    // Sponsor: add SOL to the pool that matches record_donation donations 1:1
+    pub fn fund_matching_pool(ctx: Context<FundMatchingPool>, amount: u64) -> Result<()> {
+        require!(amount > 0, DonationError::ZeroDonation);
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.sponsor.to_account_info(),
+                    to: ctx.accounts.matching_pool.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let matching_pool = &mut ctx.accounts.matching_pool;
+        matching_pool.total_funded = matching_pool.total_funded
+            .checked_add(amount)
+            .ok_or(DonationError::MathOverflow)?;
+
+        msg!(
+            "MATCHING_POOL_FUNDED_EVENT: sponsor={}, amount={}, total_funded={}",
+            ctx.accounts.sponsor.key(),
+            amount,
+            matching_pool.total_funded
+        );
+
+        Ok(())
+    }
+
+    // Donate the configured SPL payment token into the program's payment vault
+    pub fn record_spl_donation(ctx: Context<RecordSplDonation>, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.config.paused, DonationError::DonationsPaused);
+        require!(amount > 0, DonationError::ZeroDonation);
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.payment_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.donor_payment_account.to_account_info(),
+                    mint: ctx.accounts.payment_mint.to_account_info(),
+                    to: ctx.accounts.payment_vault.to_account_info(),
+                    authority: ctx.accounts.donor.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.payment_mint.decimals,
+        )?;
+
+        let config = &ctx.accounts.config;
+        let tokens_minted = (amount as u128)
+            .checked_mul(config.payment_rate_numerator as u128)
+            .and_then(|value| value.checked_div(config.payment_rate_denominator as u128))
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(DonationError::MathOverflow)?;
+        require!(tokens_minted > 0, DonationError::BelowMinimum);
+
+        create_reward_account(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.donor,
+            &ctx.accounts.donor,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.spacex_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program,
+        )?;
+        mint_reward(
+            &ctx.accounts.token_program,
+            &ctx.accounts.spacex_mint,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            tokens_minted,
+        )?;
+
+        emit!(SplDonationReceived {
+            donor: ctx.accounts.donor.key(),
+            payment_mint: ctx.accounts.payment_mint.key(),
+            amount,
+            tokens_minted,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // Pre-fund `installments` donations of `amount`, one every `interval` seconds,
+    // which anyone can then crank into the vault with execute_recurring
+    pub fn create_recurring(
+        ctx: Context<CreateRecurring>,
+        amount: u64,
+        interval: i64,
+        installments: u32,
+    ) -> Result<()> {
+        validate_donation(&ctx.accounts.config, amount)?;
+        require!(interval > 0 && installments > 0, DonationError::InvalidSchedule);
+
+        let prefund = amount
+            .checked_mul(installments as u64)
+            .ok_or(DonationError::MathOverflow)?;
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.donor.to_account_info(),
+                    to: ctx.accounts.schedule.to_account_info(),
+                },
+            ),
+            prefund,
+        )?;
+
+        let schedule = &mut ctx.accounts.schedule;
+        schedule.donor = ctx.accounts.donor.key();
+        schedule.amount = amount;
+        schedule.interval = interval;
+        // The first installment is due immediately
+        schedule.next_due_ts = Clock::get()?.unix_timestamp;
+        schedule.remaining = installments;
+        schedule.bump = ctx.bumps.schedule;
+
         msg!(
-            "DONATION_EVENT: donor={}, amount={}, timestamp={}", 
+            "RECURRING_CREATED_EVENT: donor={}, amount={}, interval={}, installments={}",
+            schedule.donor,
+            amount,
+            interval,
+            installments
+        );
+
+        Ok(())
+    }
+
+    // Permissionless crank: move the next due installment into the vault and reward
+    // the donor; the cranker pays for any of the donor's accounts that do not exist yet
+    pub fn execute_recurring(ctx: Context<ExecuteRecurring>) -> Result<()> {
+        require!(!ctx.accounts.config.paused, DonationError::DonationsPaused);
+        let now = Clock::get()?.unix_timestamp;
+        let schedule = &mut ctx.accounts.schedule;
+        require!(schedule.remaining > 0, DonationError::ScheduleComplete);
+        require!(now >= schedule.next_due_ts, DonationError::InstallmentNotDue);
+
+        // The schedule is owned by this program, so lamports move directly
+        let amount = schedule.amount;
+        let schedule_info = schedule.to_account_info();
+        **schedule_info.try_borrow_mut_lamports()? = schedule_info
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(DonationError::MathOverflow)?;
+        let vault_info = ctx.accounts.vault.to_account_info();
+        **vault_info.try_borrow_mut_lamports()? = vault_info
+            .lamports()
+            .checked_add(amount)
+            .ok_or(DonationError::MathOverflow)?;
+
+        schedule.remaining -= 1;
+        schedule.next_due_ts = schedule.next_due_ts
+            .checked_add(schedule.interval)
+            .ok_or(DonationError::MathOverflow)?;
+
+        associated_token::create_idempotent(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            Create {
+                payer: ctx.accounts.cranker.to_account_info(),
+                associated_token: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.donor.to_account_info(),
+                mint: ctx.accounts.spacex_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+        let sol_usd = sol_usd_price(&ctx.accounts.config, ctx.accounts.price_update.as_ref())?;
+        let tokens_minted = reward_for_lamports(&ctx.accounts.config, sol_usd, amount)?;
+        mint_reward(
+            &ctx.accounts.token_program,
+            &ctx.accounts.spacex_mint,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            tokens_minted,
+        )?;
+
+        // Installments count towards lifetime totals, badges and the leaderboard
+        // like any record_donation donation
+        record_lifetime_totals(
+            &mut ctx.accounts.donor_stats,
+            &mut ctx.accounts.global_stats,
             ctx.accounts.donor.key(),
             amount,
-            clock.unix_timestamp
+            now,
+        )?;
+        update_leaderboard(
+            &mut ctx.accounts.leaderboard,
+            ctx.accounts.donor.key(),
+            ctx.accounts.donor_stats.total_donated,
         );
-        
+        let accounts = &mut ctx.accounts;
+        award_badges(
+            &accounts.config,
+            &mut accounts.donor_stats,
+            &accounts.donor,
+            &accounts.cranker,
+            [
+                (&*accounts.bronze_mint, &accounts.bronze_token_account),
+                (&*accounts.silver_mint, &accounts.silver_token_account),
+                (&*accounts.gold_mint, &accounts.gold_token_account),
+            ],
+            &accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            (&accounts.associated_token_program, &accounts.system_program, &accounts.token_program),
+        )?;
+
+        emit!(DonationReceived {
+            donor: ctx.accounts.donor.key(),
+            lamports: amount,
+            fee_lamports: 0,
+            net_lamports: amount,
+            tokens_minted,
+            bonus_tokens: 0,
+            campaign: Pubkey::default(),
+            paid_in_wsol: false,
+            timestamp: now,
+        });
+
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct RecordDonation<'info> {
-    #[account(mut)]
-    pub donor: Signer<'info>,
-    /// CHECK: This is the PDA that will receive the donations
-    #[account(
-        mut,
-        seeds = [b"donation_vault"],
-        bump
-    )]
-    pub vault: AccountInfo<'info>,
-    pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+    // Donor: stop a recurring schedule and take back the unspent pre-funding
+    pub fn cancel_recurring(ctx: Context<CancelRecurring>) -> Result<()> {
+        msg!(
+            "RECURRING_CANCELLED_EVENT: donor={}, remaining={}",
+            ctx.accounts.donor.key(),
+            ctx.accounts.schedule.remaining
+        );
+        Ok(())
+    }
+
+    // Donate to a campaign during its window. The platform fee goes to the fee
+    // vault and the remainder to the campaign's own vault. `memo` is an optional
+    // message for the campaign page, stored in the donation record. Donors can
+    // pay in native SOL or, by passing the wSOL accounts, in wSOL
+    pub fn donate_to_campaign(
+        ctx: Context<DonateToCampaign>,
+        amount: u64,
+        memo: String,
+    ) -> Result<()> {
+        validate_donation(&ctx.accounts.config, amount)?;
+        require!(memo.len() <= MAX_DONATION_MEMO_LEN, DonationError::MemoTooLong);
+        let clock = Clock::get()?;
+        require!(
+            !ctx.accounts.campaign.claimed && !ctx.accounts.campaign.cancelled,
+            DonationError::CampaignClosed
+        );
+        require!(
+            clock.unix_timestamp >= ctx.accounts.campaign.start_ts
+                && clock.unix_timestamp < ctx.accounts.campaign.end_ts,
+            DonationError::CampaignNotActive
+        );
+
+        // Running total for this donor, checked against the campaign's cap
+        let contribution = &mut ctx.accounts.contribution;
+        contribution.campaign = ctx.accounts.campaign.key();
+        contribution.donor = ctx.accounts.donor.key();
+        contribution.total_donated = contribution.total_donated
+            .checked_add(amount)
+            .ok_or(DonationError::MathOverflow)?;
+        let max_per_donor = ctx.accounts.campaign.max_per_donor;
+        require!(
+            max_per_donor == 0 || contribution.total_donated <= max_per_donor,
+            DonationError::DonorCapExceeded
+        );
+
+        // A donor paying in wSOL moves exactly `amount` into a temporary account
+        // owned by the mint authority PDA, which is closed into their wallet; the
+        // donor gets back its rent plus `amount` in native SOL, so the rest of the
+        // donation is the same native SOL transfer and their own wSOL account stays
+        let paid_in_wsol = ctx.accounts.wsol_account.is_some();
+        if let Some(wsol_account) = &ctx.accounts.wsol_account {
+            require!(
+                wsol_account.amount >= amount,
+                DonationError::InsufficientWrappedSol
+            );
+            let wsol_temp = ctx
+                .accounts
+                .wsol_temp
+                .as_ref()
+                .ok_or(DonationError::NotWrappedSol)?;
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: wsol_account.to_account_info(),
+                        to: wsol_temp.to_account_info(),
+                        authority: ctx.accounts.donor.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+
+            let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+            let signer_seeds = &[&seeds[..]];
+            token::close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: wsol_temp.to_account_info(),
+                    destination: ctx.accounts.donor.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+
+        // Decided on the total raised before this donation
+        let early_bird = is_early_bird(
+            &ctx.accounts.config,
+            &ctx.accounts.campaign,
+            clock.unix_timestamp,
+        )?;
+
+        let fee = platform_fee(&ctx.accounts.config, amount)?;
+        let net = amount
+            .checked_sub(fee)
+            .ok_or(DonationError::MathOverflow)?;
+        for (to, lamports) in [
+            (ctx.accounts.fee_vault.to_account_info(), fee),
+            (ctx.accounts.campaign_vault.to_account_info(), net),
+        ] {
+            if lamports == 0 {
+                continue;
+            }
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.donor.to_account_info(),
+                        to,
+                    },
+                ),
+                lamports,
+            )?;
+        }
+
+        // Only the net amount is in the campaign vault, so it is what counts
+        // towards the goal and what a refund returns
+        let campaign = &mut ctx.accounts.campaign;
+        // The record PDA was derived from the count before this donation
+        let index = campaign.donation_count;
+        campaign.raised = campaign.raised
+            .checked_add(net)
+            .ok_or(DonationError::MathOverflow)?;
+        campaign.donation_count = campaign.donation_count
+            .checked_add(1)
+            .ok_or(DonationError::MathOverflow)?;
+        if index == 0 {
+            campaign.first_donation_ts = clock.unix_timestamp;
+            emit!(FirstDonationReceived {
+                campaign: campaign.key(),
+                donor: ctx.accounts.donor.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        campaign.last_donation_ts = clock.unix_timestamp;
+
+        create_reward_account(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.donor,
+            &ctx.accounts.donor,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.reward_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program,
+        )?;
+        let sol_usd = sol_usd_price(&ctx.accounts.config, ctx.accounts.price_update.as_ref())?;
+        let base_tokens = reward_for_lamports(&ctx.accounts.config, sol_usd, amount)?;
+        let bonus_tokens = if early_bird {
+            (base_tokens as u128)
+                .checked_mul(ctx.accounts.config.early_bird_multiplier_bps as u128)
+                .map(|value| value / 10_000)
+                .and_then(|value| value.checked_sub(base_tokens as u128))
+                .and_then(|value| u64::try_from(value).ok())
+                .ok_or(DonationError::MathOverflow)?
+        } else {
+            0
+        };
+        let tokens_minted = base_tokens
+            .checked_add(bonus_tokens)
+            .ok_or(DonationError::MathOverflow)?;
+        if ctx.accounts.config.vesting_duration_seconds > 0 {
+            ctx.accounts.vest_reward(
+                ctx.bumps.mint_authority,
+                ctx.bumps.reward_vesting,
+                tokens_minted,
+                clock.unix_timestamp,
+            )?;
+        } else {
+            mint_reward(
+                &ctx.accounts.token_program,
+                &ctx.accounts.reward_mint,
+                &ctx.accounts.user_token_account,
+                &ctx.accounts.mint_authority,
+                ctx.bumps.mint_authority,
+                tokens_minted,
+            )?;
+        }
+
+        // History entry for pagination, and proof of this contribution for refund
+        let record = &mut ctx.accounts.donation_record;
+        record.donor = ctx.accounts.donor.key();
+        record.campaign = ctx.accounts.campaign.key();
+        record.index = index;
+        record.lamports = net;
+        record.tokens_minted = tokens_minted;
+        record.timestamp = clock.unix_timestamp;
+        record.refunded = false;
+        record.memo = memo;
+        record.bump = ctx.bumps.donation_record;
+
+        // Large donations also earn a one-of-one receipt NFT naming the campaign
+        let threshold = ctx.accounts.config.receipt_threshold_lamports;
+        if threshold > 0 && amount >= threshold {
+            let accounts = &ctx.accounts;
+            mint_receipt_nft(
+                &accounts.config,
+                (
+                    accounts.receipt_mint.as_deref(),
+                    accounts.receipt_token_account.as_ref(),
+                    accounts.receipt_metadata.as_ref(),
+                    accounts.receipt_master_edition.as_ref(),
+                    accounts.token_metadata_program.as_ref(),
+                    accounts.rent.as_ref(),
+                ),
+                &accounts.donor,
+                &accounts.donor,
+                &accounts.mint_authority,
+                ctx.bumps.mint_authority,
+                (&accounts.associated_token_program, &accounts.system_program, &accounts.token_program),
+                amount,
+                accounts.campaign.key(),
+                accounts.donor.key(),
+            )?;
+        }
+
+        emit!(DonationReceived {
+            donor: ctx.accounts.donor.key(),
+            lamports: amount,
+            fee_lamports: fee,
+            net_lamports: net,
+            tokens_minted,
+            bonus_tokens,
+            campaign: ctx.accounts.campaign.key(),
+            paid_in_wsol,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // Donor: move every vested reward token from their vesting vault to their ATA
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        let vesting = &ctx.accounts.reward_vesting;
+        let claimable = vesting
+            .vested(Clock::get()?.unix_timestamp)?
+            .checked_sub(vesting.claimed)
+            .ok_or(DonationError::MathOverflow)?;
+        require!(claimable > 0, DonationError::NothingToClaim);
+
+        create_reward_account(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.donor,
+            &ctx.accounts.donor,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.reward_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program,
+        )?;
+
+        // The mint authority PDA also owns every vesting vault
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.vesting_vault.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            claimable,
+        )?;
+
+        let vesting = &mut ctx.accounts.reward_vesting;
+        vesting.claimed = vesting.claimed
+            .checked_add(claimable)
+            .ok_or(DonationError::MathOverflow)?;
+
+        msg!(
+            "REWARDS_CLAIMED_EVENT: donor={}, campaign={}, amount={}, claimed={}, total={}",
+            vesting.donor,
+            vesting.campaign,
+            claimable,
+            vesting.claimed,
+            vesting.total
+        );
+
+        Ok(())
+    }
+
+    // Donor: burn `amount` of a campaign's reward tokens for the same amount of
+    // voting power in that campaign, for future votes on how its funds are used
+    pub fn convert_to_votes(ctx: Context<ConvertToVotes>, amount: u64) -> Result<()> {
+        require!(amount > 0, DonationError::ZeroVoteAmount);
+
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.donor.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let vote_power = &mut ctx.accounts.vote_power;
+        vote_power.campaign = ctx.accounts.campaign.key();
+        vote_power.donor = ctx.accounts.donor.key();
+        vote_power.votes = vote_power.votes
+            .checked_add(amount)
+            .ok_or(DonationError::MathOverflow)?;
+        vote_power.bump = ctx.bumps.vote_power;
+
+        msg!(
+            "VOTES_CREDITED_EVENT: donor={}, campaign={}, burned={}, votes={}",
+            vote_power.donor,
+            vote_power.campaign,
+            amount,
+            vote_power.votes
+        );
+
+        Ok(())
+    }
+
+    // Creator: set the campaign's description and image links for frontends. The
+    // name is part of the campaign's address, so it cannot change
+    pub fn update_campaign_metadata(
+        ctx: Context<UpdateCampaignMetadata>,
+        description_uri: String,
+        image_uri: String,
+    ) -> Result<()> {
+        require!(
+            description_uri.len() <= MAX_CAMPAIGN_URI_LEN && image_uri.len() <= MAX_CAMPAIGN_URI_LEN,
+            DonationError::CampaignUriTooLong
+        );
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.description_uri = description_uri;
+        campaign.image_uri = image_uri;
+
+        msg!(
+            "CAMPAIGN_UPDATED_EVENT: campaign={}, description_uri={}, image_uri={}",
+            campaign.key(),
+            campaign.description_uri,
+            campaign.image_uri
+        );
+
+        Ok(())
+    }
+
+    // Beneficiary: publish a progress update, e.g. how funds were spent, as a
+    // short text or a link; updates are numbered in order from 0
+    pub fn post_update(ctx: Context<PostUpdate>, uri_or_text: String) -> Result<()> {
+        require!(
+            !uri_or_text.is_empty() && uri_or_text.len() <= MAX_UPDATE_LEN,
+            DonationError::InvalidUpdate
+        );
+        let campaign = &mut ctx.accounts.campaign;
+        let update = &mut ctx.accounts.campaign_update;
+        update.campaign = campaign.key();
+        update.index = campaign.update_count;
+        update.content = uri_or_text;
+        update.timestamp = Clock::get()?.unix_timestamp;
+        update.bump = ctx.bumps.campaign_update;
+
+        campaign.update_count = campaign.update_count
+            .checked_add(1)
+            .ok_or(DonationError::MathOverflow)?;
+
+        msg!(
+            "CAMPAIGN_UPDATE_EVENT: campaign={}, index={}",
+            update.campaign,
+            update.index
+        );
+
+        Ok(())
+    }
+
+    // Creator: cancel a campaign before it ends; it stops accepting donations and
+    // every donation becomes refundable straight away
+    pub fn cancel_campaign(ctx: Context<CancelCampaign>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        require!(
+            !campaign.claimed && !campaign.cancelled,
+            DonationError::CampaignClosed
+        );
+        require!(
+            Clock::get()?.unix_timestamp < campaign.end_ts,
+            DonationError::CampaignEnded
+        );
+        campaign.cancelled = true;
+        let campaign_key = campaign.key();
+        close_donation_window(campaign, campaign_key);
+
+        msg!(
+            "CAMPAIGN_CANCELLED_EVENT: campaign={}, raised={}, donations={}",
+            campaign.key(),
+            campaign.raised,
+            campaign.donation_count
+        );
+
+        Ok(())
+    }
+
+    // Return a contribution to its donor once a campaign has ended short of its
+    // goal, or at any time after it was cancelled
+    pub fn refund(ctx: Context<Refund>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        if !campaign.cancelled {
+            require!(
+                Clock::get()?.unix_timestamp >= campaign.end_ts,
+                DonationError::CampaignStillActive
+            );
+            require!(
+                campaign.raised < campaign.goal_lamports,
+                DonationError::GoalReached
+            );
+        }
+        require!(
+            !ctx.accounts.donation_record.refunded,
+            DonationError::AlreadyRefunded
+        );
+
+        let amount = ctx.accounts.donation_record.lamports;
+        ensure_rent_exempt_after(&ctx.accounts.campaign_vault, amount)?;
+        let campaign_key = campaign.key();
+        let seeds = &[
+            b"vault".as_ref(),
+            campaign_key.as_ref(),
+            &[ctx.bumps.campaign_vault],
+        ];
+        invoke_signed(
+            &system_instruction::transfer(
+                &ctx.accounts.campaign_vault.key(),
+                &ctx.accounts.donor.key(),
+                amount,
+            ),
+            &[
+                ctx.accounts.campaign_vault.to_account_info(),
+                ctx.accounts.donor.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&seeds[..]],
+        )?;
+
+        ctx.accounts.donation_record.refunded = true;
+        // A refunded donation no longer counts towards the donor's cap
+        let contribution = &mut ctx.accounts.contribution;
+        contribution.total_donated = contribution.total_donated
+            .checked_sub(amount)
+            .ok_or(DonationError::MathOverflow)?;
+        // A no-op after a cancellation or an earlier refund
+        close_donation_window(&mut ctx.accounts.campaign, campaign_key);
+
+        msg!(
+            "REFUND_EVENT: donor={}, campaign={}, amount={}",
+            ctx.accounts.donor.key(),
+            campaign_key,
+            amount
+        );
+
+        Ok(())
+    }
+
+    // Beneficiary: withdraw everything a campaign raised once it met its goal; the
+    // platform fee was already split off when each donation arrived
+    pub fn claim_funds(ctx: Context<ClaimFunds>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        require!(
+            campaign.raised >= campaign.goal_lamports,
+            DonationError::GoalNotReached
+        );
+        require!(!campaign.claimed, DonationError::AlreadyClaimed);
+        require!(!campaign.cancelled, DonationError::CampaignCancelled);
+        // A revoked verification blocks the payout
+        require!(
+            !campaign.require_verified || ctx.accounts.verified_beneficiary.is_some(),
+            DonationError::BeneficiaryNotVerified
+        );
+
+        // Everything above the vault's rent reserve
+        let campaign_vault = &ctx.accounts.campaign_vault;
+        let payout = campaign_vault
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(campaign_vault.data_len()));
+        ensure_rent_exempt_after(campaign_vault, payout)?;
+        let campaign_key = campaign.key();
+        let seeds = &[
+            b"vault".as_ref(),
+            campaign_key.as_ref(),
+            &[ctx.bumps.campaign_vault],
+        ];
+        invoke_signed(
+            &system_instruction::transfer(
+                &ctx.accounts.campaign_vault.key(),
+                &ctx.accounts.beneficiary.key(),
+                payout,
+            ),
+            &[
+                ctx.accounts.campaign_vault.to_account_info(),
+                ctx.accounts.beneficiary.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&seeds[..]],
+        )?;
+
+        // Claiming ends the campaign, so the last donation is now known
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.claimed = true;
+        close_donation_window(campaign, campaign_key);
+
+        msg!(
+            "CAMPAIGN_CLAIMED_EVENT: campaign={}, beneficiary={}, payout={}",
+            campaign_key,
+            ctx.accounts.beneficiary.key(),
+            payout
+        );
+
+        Ok(())
+    }
+
+    // Admin: add `beneficiary` to the verified registry
+    pub fn verify_beneficiary(ctx: Context<VerifyBeneficiary>, beneficiary: Pubkey) -> Result<()> {
+        let verified = &mut ctx.accounts.verified_beneficiary;
+        verified.beneficiary = beneficiary;
+        verified.verified_by = ctx.accounts.admin.key();
+        verified.verified_at = Clock::get()?.unix_timestamp;
+        verified.bump = ctx.bumps.verified_beneficiary;
+        msg!("BENEFICIARY_VERIFIED_EVENT: beneficiary={}", beneficiary);
+        Ok(())
+    }
+
+    // Admin: remove a beneficiary from the verified registry, closing its entry
+    pub fn revoke_verification(ctx: Context<RevokeVerification>) -> Result<()> {
+        msg!(
+            "BENEFICIARY_REVOKED_EVENT: beneficiary={}",
+            ctx.accounts.verified_beneficiary.beneficiary
+        );
+        Ok(())
+    }
+
+    // Admin: set who approves vault withdrawals and how many approvals one needs.
+    // Once approvers exist, changing them also needs `threshold` of the current
+    // approvers to sign, passed as remaining accounts
+    pub fn set_approvers(
+        ctx: Context<UpdateConfig>,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(
+            approvers.len() <= MAX_APPROVERS
+                && threshold > 0
+                && threshold as usize <= approvers.len(),
+            DonationError::InvalidApprovers
+        );
+        require!(
+            approvers
+                .iter()
+                .enumerate()
+                .all(|(i, approver)| !approvers[..i].contains(approver)),
+            DonationError::InvalidApprovers
+        );
+
+        let config = &mut ctx.accounts.config;
+        if !config.approvers.is_empty() {
+            let mut signed: Vec<Pubkey> = Vec::new();
+            for account in ctx.remaining_accounts.iter() {
+                if account.is_signer
+                    && config.approvers.contains(account.key)
+                    && !signed.contains(account.key)
+                {
+                    signed.push(*account.key);
+                }
+            }
+            require!(
+                signed.len() >= config.approval_threshold as usize,
+                DonationError::NotEnoughApprovals
+            );
+        }
+
+        config.approvers = approvers;
+        config.approval_threshold = threshold;
+        msg!(
+            "Withdrawal approvers set: {} of {}",
+            threshold,
+            config.approvers.len()
+        );
+        Ok(())
+    }
+
+    // Approver: propose moving `amount` lamports from the vault to `destination`;
+    // the proposal counts as the proposer's approval
+    pub fn propose_withdrawal(
+        ctx: Context<ProposeWithdrawal>,
+        amount: u64,
+        destination: Pubkey,
+    ) -> Result<()> {
+        require!(amount > 0, DonationError::ZeroDonation);
+        let config = &mut ctx.accounts.config;
+        let proposer = ctx.accounts.proposer.key();
+        require!(config.approvers.contains(&proposer), DonationError::NotAnApprover);
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.index = config.proposal_count;
+        proposal.proposer = proposer;
+        proposal.destination = destination;
+        proposal.amount = amount;
+        proposal.approvals = vec![proposer];
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+
+        config.proposal_count = config.proposal_count
+            .checked_add(1)
+            .ok_or(DonationError::MathOverflow)?;
+
+        msg!(
+            "WITHDRAWAL_PROPOSED_EVENT: proposal={}, proposer={}, destination={}, amount={}",
+            proposal.index,
+            proposer,
+            destination,
+            amount
+        );
+
+        Ok(())
+    }
+
+    // Approver: approve a pending withdrawal proposal
+    pub fn approve_withdrawal(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+        let approver = ctx.accounts.approver.key();
+        require!(
+            ctx.accounts.config.approvers.contains(&approver),
+            DonationError::NotAnApprover
+        );
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.executed, DonationError::ProposalExecuted);
+        require!(!proposal.approvals.contains(&approver), DonationError::AlreadyApproved);
+        proposal.approvals.push(approver);
+
+        msg!(
+            "WITHDRAWAL_APPROVED_EVENT: proposal={}, approver={}, approvals={}",
+            proposal.index,
+            approver,
+            proposal.approvals.len()
+        );
+
+        Ok(())
+    }
+
+    // Anyone: pay out a proposal once enough current approvers have approved it,
+    // never dropping the vault below rent exemption
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let proposal = &ctx.accounts.proposal;
+        require!(!proposal.executed, DonationError::ProposalExecuted);
+        // Approvals from keys that were removed since no longer count
+        let approvals = proposal.approvals
+            .iter()
+            .filter(|approver| config.approvers.contains(approver))
+            .count();
+        require!(
+            config.approval_threshold > 0 && approvals >= config.approval_threshold as usize,
+            DonationError::NotEnoughApprovals
+        );
+
+        let amount = proposal.amount;
+        let vault = &ctx.accounts.vault;
+        ensure_rent_exempt_after(vault, amount)?;
+
+        // The vault is a system-owned PDA, so it signs the transfer with its seeds
+        let seeds = &[b"donation_vault".as_ref(), &[ctx.bumps.vault]];
+        invoke_signed(
+            &system_instruction::transfer(&vault.key(), &ctx.accounts.destination.key(), amount),
+            &[
+                vault.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&seeds[..]],
+        )?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.executed = true;
+
+        msg!(
+            "WITHDRAW_EVENT: proposal={}, destination={}, amount={}",
+            proposal.index,
+            proposal.destination,
+            amount
+        );
+
+        Ok(())
+    }
+
+    // Admin: schedule an emergency withdrawal from the donation vault that bypasses
+    // the approvers, executable only after EMERGENCY_TIMELOCK_SECONDS so donors and
+    // approvers can see it coming
+    pub fn initiate_emergency_withdraw(
+        ctx: Context<InitiateEmergencyWithdraw>,
+        amount: u64,
+        destination: Pubkey,
+    ) -> Result<()> {
+        require!(amount > 0, DonationError::ZeroDonation);
+        let now = Clock::get()?.unix_timestamp;
+        let emergency = &mut ctx.accounts.emergency_withdrawal;
+        emergency.amount = amount;
+        emergency.destination = destination;
+        emergency.initiated_ts = now;
+        emergency.unlock_ts = now
+            .checked_add(EMERGENCY_TIMELOCK_SECONDS)
+            .ok_or(DonationError::MathOverflow)?;
+        emergency.bump = ctx.bumps.emergency_withdrawal;
+
+        msg!(
+            "EMERGENCY_WITHDRAW_INITIATED_EVENT: destination={}, amount={}, unlock_ts={}",
+            destination,
+            amount,
+            emergency.unlock_ts
+        );
+
+        Ok(())
+    }
+
+    // Admin: carry out the pending emergency withdrawal once its timelock elapsed
+    pub fn execute_emergency_withdraw(ctx: Context<ExecuteEmergencyWithdraw>) -> Result<()> {
+        let emergency = &ctx.accounts.emergency_withdrawal;
+        require!(
+            Clock::get()?.unix_timestamp >= emergency.unlock_ts,
+            DonationError::TimelockActive
+        );
+
+        let amount = emergency.amount;
+        let vault = &ctx.accounts.vault;
+        ensure_rent_exempt_after(vault, amount)?;
+
+        let seeds = &[b"donation_vault".as_ref(), &[ctx.bumps.vault]];
+        invoke_signed(
+            &system_instruction::transfer(&vault.key(), &ctx.accounts.destination.key(), amount),
+            &[
+                vault.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&seeds[..]],
+        )?;
+
+        msg!(
+            "EMERGENCY_WITHDRAW_EVENT: destination={}, amount={}",
+            emergency.destination,
+            amount
+        );
+
+        Ok(())
+    }
+
+    // Admin or any withdrawal approver: abort the pending emergency withdrawal,
+    // returning its rent to the admin who initiated it
+    pub fn cancel_emergency_withdraw(ctx: Context<CancelEmergencyWithdraw>) -> Result<()> {
+        let canceller = ctx.accounts.canceller.key();
+        let config = &ctx.accounts.config;
+        require!(
+            canceller == config.admin || config.approvers.contains(&canceller),
+            DonationError::Unauthorized
+        );
+
+        msg!(
+            "EMERGENCY_WITHDRAW_CANCELLED_EVENT: canceller={}, destination={}, amount={}",
+            canceller,
+            ctx.accounts.emergency_withdrawal.destination,
+            ctx.accounts.emergency_withdrawal.amount
+        );
+
+        Ok(())
+    }
+
+    // Admin: move collected platform fees out of the fee vault, keeping it rent-exempt
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        let fee_vault = &ctx.accounts.fee_vault;
+        ensure_rent_exempt_after(fee_vault, amount)?;
+
+        let seeds = &[b"fee_vault".as_ref(), &[ctx.bumps.fee_vault]];
+        invoke_signed(
+            &system_instruction::transfer(&fee_vault.key(), &ctx.accounts.admin.key(), amount),
+            &[
+                fee_vault.to_account_info(),
+                ctx.accounts.admin.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&seeds[..]],
+        )?;
+
+        msg!(
+            "FEE_WITHDRAW_EVENT: admin={}, amount={}",
+            ctx.accounts.admin.key(),
+            amount
+        );
+
+        Ok(())
+    }
+}
+
+// Create the donor's ATA for `mint` at `payer`'s expense if it is missing; a no-op
+// when it already exists. Cheaper than init_if_needed and needs no rent sysvar
+fn create_reward_account<'info>(
+    associated_token_program: &Program<'info, AssociatedToken>,
+    payer: &Signer<'info>,
+    donor: &impl ToAccountInfo<'info>,
+    user_token_account: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    associated_token::create_idempotent(CpiContext::new(
+        associated_token_program.to_account_info(),
+        Create {
+            payer: payer.to_account_info(),
+            associated_token: user_token_account.clone(),
+            authority: donor.to_account_info(),
+            mint: mint.clone(),
+            system_program: system_program.to_account_info(),
+            token_program: token_program.to_account_info(),
+        },
+    ))
+}
+
+// Add a global-vault donation of `amount` at `now` to the donor's profile and the
+// program-wide totals. Returns the donation's index among all of them
+fn record_lifetime_totals(
+    donor_stats: &mut DonorStats,
+    global_stats: &mut GlobalStats,
+    donor: Pubkey,
+    amount: u64,
+    now: i64,
+) -> Result<u64> {
+    // A zero count means the profile was just created
+    let today = now.div_euclid(SECONDS_PER_DAY);
+    let is_new_donor = donor_stats.donation_count == 0;
+    if is_new_donor {
+        donor_stats.donor = donor;
+        donor_stats.first_donation_ts = now;
+    }
+    donor_stats.streak_days = donor_stats.streak_after(today);
+    donor_stats.last_donation_day = today;
+    donor_stats.total_donated = donor_stats.total_donated
+        .checked_add(amount)
+        .ok_or(DonationError::MathOverflow)?;
+    donor_stats.donation_count = donor_stats.donation_count
+        .checked_add(1)
+        .ok_or(DonationError::MathOverflow)?;
+    donor_stats.last_donation_ts = now;
+
+    let donation_index = global_stats.total_donations;
+    global_stats.total_raised = global_stats.total_raised
+        .checked_add(amount)
+        .ok_or(DonationError::MathOverflow)?;
+    global_stats.total_donations = global_stats.total_donations
+        .checked_add(1)
+        .ok_or(DonationError::MathOverflow)?;
+    if is_new_donor {
+        global_stats.unique_donors = global_stats.unique_donors
+            .checked_add(1)
+            .ok_or(DonationError::MathOverflow)?;
+    }
+    Ok(donation_index)
+}
+
+// Keep the top donors sorted by lifetime total, announcing a new leader
+fn update_leaderboard(leaderboard: &mut Leaderboard, donor: Pubkey, total_donated: u64) {
+    let previous_leader = leaderboard.leader();
+    leaderboard.record(donor, total_donated);
+    let new_leader = leaderboard.leader();
+    if new_leader != previous_leader {
+        emit!(LeaderChanged {
+            previous_leader,
+            new_leader,
+            total_donated,
+        });
+    }
+}
+
+// Mint one badge token for every tier the donor's lifetime total has reached but
+// that they have not earned yet, and record it in their stats. `badges` pairs each
+// BADGE_NAMES mint with the donor's ATA for it
+#[allow(clippy::too_many_arguments)]
+fn award_badges<'info>(
+    config: &Config,
+    donor_stats: &mut DonorStats,
+    donor: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    badges: [(&Account<'info, Mint>, &AccountInfo<'info>); 3],
+    mint_authority: &AccountInfo<'info>,
+    mint_authority_bump: u8,
+    (associated_token_program, system_program, token_program): (
+        &Program<'info, AssociatedToken>,
+        &Program<'info, System>,
+        &Program<'info, Token>,
+    ),
+) -> Result<()> {
+    let total_donated = donor_stats.total_donated;
+    for (tier, (badge_mint, badge_token_account)) in badges.into_iter().enumerate() {
+        let threshold = config.badge_thresholds[tier];
+        let bit = 1u8 << tier;
+        if threshold == 0 || total_donated < threshold || donor_stats.badges & bit != 0 {
+            continue;
+        }
+
+        create_reward_account(
+            associated_token_program,
+            payer,
+            donor,
+            badge_token_account,
+            &badge_mint.to_account_info(),
+            system_program,
+            token_program,
+        )?;
+        mint_reward(
+            token_program,
+            badge_mint,
+            badge_token_account,
+            mint_authority,
+            mint_authority_bump,
+            1,
+        )?;
+        donor_stats.badges |= bit;
+
+        msg!(
+            "BADGE_EARNED_EVENT: donor={}, tier={}, total_donated={}",
+            donor.key(),
+            BADGE_NAMES[tier],
+            total_donated
+        );
+    }
+    Ok(())
+}
+
+// Mint a one-of-one receipt NFT to the donor; the amount goes in the name and
+// both amount and campaign go in the uri query, with Pubkey::default() standing
+// for the global vault. `receipt` holds the optional receipt accounts: mint, the
+// donor's ATA for it, metadata, master edition, metadata program and rent.
+// `public_donor` is what the log shows, Pubkey::default() for anonymous donors
+#[allow(clippy::too_many_arguments)]
+fn mint_receipt_nft<'info>(
+    config: &Config,
+    receipt: (
+        Option<&Account<'info, Mint>>,
+        Option<&AccountInfo<'info>>,
+        Option<&UncheckedAccount<'info>>,
+        Option<&UncheckedAccount<'info>>,
+        Option<&Program<'info, Metadata>>,
+        Option<&Sysvar<'info, Rent>>,
+    ),
+    donor: &Signer<'info>,
+    payer: &Signer<'info>,
+    mint_authority: &AccountInfo<'info>,
+    mint_authority_bump: u8,
+    (associated_token_program, system_program, token_program): (
+        &Program<'info, AssociatedToken>,
+        &Program<'info, System>,
+        &Program<'info, Token>,
+    ),
+    amount: u64,
+    campaign: Pubkey,
+    public_donor: Pubkey,
+) -> Result<()> {
+    let (
+        Some(receipt_mint),
+        Some(receipt_token_account),
+        Some(metadata),
+        Some(master_edition),
+        Some(token_metadata_program),
+        Some(rent),
+    ) = receipt
+    else {
+        return err!(DonationError::MissingReceiptAccounts);
+    };
+
+    create_reward_account(
+        associated_token_program,
+        payer,
+        donor,
+        receipt_token_account,
+        &receipt_mint.to_account_info(),
+        system_program,
+        token_program,
+    )?;
+    mint_reward(
+        token_program,
+        receipt_mint,
+        receipt_token_account,
+        mint_authority,
+        mint_authority_bump,
+        1,
+    )?;
+
+    let seeds = &[b"mint_authority".as_ref(), &[mint_authority_bump]];
+    let signer_seeds = &[&seeds[..]];
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: metadata.to_account_info(),
+                mint: receipt_mint.to_account_info(),
+                mint_authority: mint_authority.to_account_info(),
+                payer: payer.to_account_info(),
+                update_authority: mint_authority.to_account_info(),
+                system_program: system_program.to_account_info(),
+                rent: rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        DataV2 {
+            name: format!("Donation {} lamports", amount),
+            symbol: "DONATE".to_string(),
+            uri: format!(
+                "{}?amount={}&campaign={}",
+                config.receipt_uri,
+                amount,
+                campaign
+            ),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        false,
+        true,
+        None,
+    )?;
+
+    // A master edition with max_supply 0 makes the receipt a true one-of-one
+    create_master_edition_v3(
+        CpiContext::new_with_signer(
+            token_metadata_program.to_account_info(),
+            CreateMasterEditionV3 {
+                edition: master_edition.to_account_info(),
+                mint: receipt_mint.to_account_info(),
+                update_authority: mint_authority.to_account_info(),
+                mint_authority: mint_authority.to_account_info(),
+                payer: payer.to_account_info(),
+                metadata: metadata.to_account_info(),
+                token_program: token_program.to_account_info(),
+                system_program: system_program.to_account_info(),
+                rent: rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        Some(0),
+    )?;
+
+    msg!(
+        "Receipt NFT {} minted to {}",
+        receipt_mint.key(),
+        public_donor
+    );
+
+    Ok(())
+}
+
+// Announce that a campaign's donations are final the first time it closes: when
+// it is claimed or cancelled, or on the first refund after it ended
+fn close_donation_window(campaign: &mut Campaign, campaign_key: Pubkey) {
+    if campaign.window_closed {
+        return;
+    }
+    campaign.window_closed = true;
+    emit!(DonationWindowClosed {
+        campaign: campaign_key,
+        first_donation_ts: campaign.first_donation_ts,
+        last_donation_ts: campaign.last_donation_ts,
+        donation_count: campaign.donation_count,
+        raised: campaign.raised,
+    });
+}
+
+// Reject donations while paused, and empty or below-minimum ones, before any SOL moves
+fn validate_donation(config: &Config, amount: u64) -> Result<()> {
+    require!(!config.paused, DonationError::DonationsPaused);
+    require!(amount > 0, DonationError::ZeroDonation);
+    require!(
+        amount >= config.min_donation_lamports,
+        DonationError::BelowMinimum
+    );
+    Ok(())
+}
+
+// Every path that pays out of a vault PDA checks it still holds its rent-exempt
+// minimum afterwards; a partly drained vault would be rejected by the runtime
+fn ensure_rent_exempt_after(vault: &AccountInfo, amount: u64) -> Result<()> {
+    let remaining = vault
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(DonationError::InsufficientVaultBalance)?;
+    require!(
+        remaining >= Rent::get()?.minimum_balance(vault.data_len()),
+        DonationError::WouldBreachRentExemption
+    );
+    Ok(())
+}
+
+// Whether a donation to `campaign` at `now` earns the early-bird bonus: before it
+// has raised early_bird_goal_bps of its goal, or within early_bird_seconds of start
+fn is_early_bird(config: &Config, campaign: &Campaign, now: i64) -> Result<bool> {
+    if config.early_bird_multiplier_bps == 0 {
+        return Ok(false);
+    }
+    let below_goal = if config.early_bird_goal_bps > 0 {
+        // raised / goal < goal_bps / 10000, cross-multiplied in u128
+        let raised_bps = (campaign.raised as u128)
+            .checked_mul(10_000)
+            .ok_or(DonationError::MathOverflow)?;
+        let threshold = (campaign.goal_lamports as u128)
+            .checked_mul(config.early_bird_goal_bps as u128)
+            .ok_or(DonationError::MathOverflow)?;
+        raised_bps < threshold
+    } else {
+        false
+    };
+    let before_cutoff = if config.early_bird_seconds > 0 {
+        let cutoff = campaign.start_ts
+            .checked_add(config.early_bird_seconds)
+            .ok_or(DonationError::MathOverflow)?;
+        now < cutoff
+    } else {
+        false
+    };
+    Ok(below_goal || before_cutoff)
+}
+
+// Platform fee on a campaign donation of `lamports`, rounded down
+fn platform_fee(config: &Config, lamports: u64) -> Result<u64> {
+    (lamports as u128)
+        .checked_mul(config.fee_bps as u128)
+        .and_then(|value| value.checked_div(10_000))
+        .and_then(|value| u64::try_from(value).ok())
+        .ok_or(error!(DonationError::MathOverflow))
+}
+
+// A validated Pyth SOL/USD price: `price * 10^exponent` dollars per SOL
+#[derive(Clone, Copy)]
+struct SolUsdPrice {
+    price: u64,
+    exponent: i32,
+}
+
+// Read SOL/USD from `price_update` when USD pricing is on, rejecting stale,
+// non-positive or low-confidence prices; None while the lamport rate applies
+fn sol_usd_price(
+    config: &Config,
+    price_update: Option<&Account<PriceUpdateV2>>,
+) -> Result<Option<SolUsdPrice>> {
+    if config.tokens_per_usd == 0 {
+        return Ok(None);
+    }
+    let price_update = price_update.ok_or(DonationError::MissingPriceUpdate)?;
+    let price = price_update.get_price_no_older_than(
+        &Clock::get()?,
+        config.max_price_age_seconds,
+        &config.sol_usd_feed_id,
+    )?;
+    require!(
+        price.price > 0 && price.exponent <= 0 && price.exponent >= -18,
+        DonationError::InvalidPrice
+    );
+    // conf / price must stay within max_price_confidence_bps / 10000
+    require!(
+        (price.conf as u128) * 10_000
+            <= (price.price as u128) * (config.max_price_confidence_bps as u128),
+        DonationError::PriceTooUncertain
+    );
+    Ok(Some(SolUsdPrice {
+        price: price.price as u64,
+        exponent: price.exponent,
+    }))
+}
+
+// Reward for a SOL donation, rejecting dust that would mint nothing
+fn reward_for_lamports(config: &Config, sol_usd: Option<SolUsdPrice>, lamports: u64) -> Result<u64> {
+    let tokens_minted = reward_amount(config, sol_usd, lamports)?;
+    require!(tokens_minted > 0, DonationError::BelowMinimum);
+    Ok(tokens_minted)
+}
+
+// Reward for `lamports` at the configured rate, or at tokens_per_usd of their USD
+// value when `sol_usd` is given, scaled by the highest reward tier the amount
+// reaches; may be zero
+fn reward_amount(config: &Config, sol_usd: Option<SolUsdPrice>, lamports: u64) -> Result<u64> {
+    let multiplier_bps = config.reward_tiers
+        .iter()
+        .rev()
+        .find(|tier| lamports >= tier.min_lamports)
+        .map_or(10_000, |tier| tier.multiplier_bps);
+    if let Some(sol_usd) = sol_usd {
+        // lamports * price * tokens_per_usd * multiplier
+        //   / (LAMPORTS_PER_SOL * 10^-exponent * 10000), divided once at the end
+        return (lamports as u128)
+            .checked_mul(sol_usd.price as u128)
+            .and_then(|value| value.checked_mul(config.tokens_per_usd as u128))
+            .and_then(|value| value.checked_mul(multiplier_bps as u128))
+            .and_then(|value| {
+                10u128
+                    .checked_pow(sol_usd.exponent.unsigned_abs())
+                    .and_then(|scale| scale.checked_mul(LAMPORTS_PER_SOL as u128 * 10_000))
+                    .and_then(|divisor| value.checked_div(divisor))
+            })
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(error!(DonationError::MathOverflow));
+    }
+    // lamports * numerator * multiplier / (denominator * 10000), in u128 and with
+    // every multiplication done before the single division to keep precision
+    (lamports as u128)
+        .checked_mul(config.rate_numerator as u128)
+        .and_then(|value| value.checked_mul(multiplier_bps as u128))
+        .and_then(|value| {
+            (config.rate_denominator as u128)
+                .checked_mul(10_000)
+                .and_then(|divisor| value.checked_div(divisor))
+        })
+        .and_then(|value| u64::try_from(value).ok())
+        .ok_or(error!(DonationError::MathOverflow))
+}
+
+// Mint `tokens` SpaceX tokens to the donor, signed by the mint authority PDA
+fn mint_reward<'info>(
+    token_program: &Program<'info, Token>,
+    spacex_mint: &Account<'info, Mint>,
+    user_token_account: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    mint_authority_bump: u8,
+    tokens: u64,
+) -> Result<()> {
+    let seeds = &[b"mint_authority".as_ref(), &[mint_authority_bump]];
+    let signer_seeds = &[&seeds[..]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: spacex_mint.to_account_info(),
+                to: user_token_account.to_account_info(),
+                authority: mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        tokens,
+    )
+}
+
+// Highest platform fee the admin can set: 10%
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+// Donor badges in tier order; each name is also the seed of its mint and its bit
+// in DonorStats::badges is 1 << index
+pub const BADGE_NAMES: [&str; 3] = ["bronze", "silver", "gold"];
+
+// Longest base uri for receipt NFTs; Metaplex allows 200 bytes including the query
+pub const MAX_RECEIPT_URI_LEN: usize = 96;
+
+// Most reward tiers the config can hold
+pub const MAX_REWARD_TIERS: usize = 4;
+
+// Donations of at least min_lamports earn multiplier_bps / 10000 times the base reward
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct RewardTier {
+    pub min_lamports: u64,
+    pub multiplier_bps: u16,
+}
+
+// Program-wide settings
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    // Platform fee split off each campaign donation, in basis points
+    pub fee_bps: u16,
+    // Sorted by min_lamports; empty means every donation earns the base rate
+    #[max_len(4)]
+    pub reward_tiers: Vec<RewardTier>,
+    // Base reward of rate_numerator / rate_denominator tokens per lamport
+    pub rate_numerator: u64,
+    pub rate_denominator: u64,
+    // Smallest donation record_donation and donate_to_campaign accept
+    pub min_donation_lamports: u64,
+    // SPL token accepted by record_spl_donation and its reward rate per base unit
+    pub payment_mint: Pubkey,
+    pub payment_rate_numerator: u64,
+    pub payment_rate_denominator: u64,
+    // Donations of at least this many lamports mint a receipt NFT; 0 disables them
+    pub receipt_threshold_lamports: u64,
+    #[max_len(96)]
+    pub receipt_uri: String,
+    // Set by pause; every donation instruction fails while true
+    pub paused: bool,
+    // Lifetime totals that earn the bronze, silver and gold badges; 0 disables one
+    pub badge_thresholds: [u64; 3],
+    // Keys that approve vault withdrawals, and how many approvals one needs
+    #[max_len(5)]
+    pub approvers: Vec<Pubkey>,
+    pub approval_threshold: u8,
+    // Number of withdrawal proposals so far; seeds each WithdrawalProposal
+    pub proposal_count: u64,
+    // Early-bird reward multiplier for campaign donations, and when it applies
+    pub early_bird_multiplier_bps: u16,
+    pub early_bird_goal_bps: u16,
+    pub early_bird_seconds: i64,
+    // Vesting schedule for campaign donation rewards; a 0 duration disables it
+    pub vesting_cliff_seconds: i64,
+    pub vesting_duration_seconds: i64,
+    // Share of a referred donor's reward minted to the referrer, in basis points
+    pub referral_bps: u16,
+    // Pyth SOL/USD pricing; tokens_per_usd of 0 keeps the lamport rate
+    pub sol_usd_feed_id: [u8; 32],
+    pub tokens_per_usd: u64,
+    pub max_price_age_seconds: u64,
+    pub max_price_confidence_bps: u16,
+    // Donation streak bonus per consecutive day, and its ceiling
+    pub streak_bonus_bps_per_day: u16,
+    pub max_streak_bonus_bps: u16,
+    pub bump: u8,
+}
+
+// Highest referral share the admin can set: half the donor's reward
+pub const MAX_REFERRAL_BPS: u16 = 5_000;
+
+// Lifetime referral totals for one referrer
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralStats {
+    pub referrer: Pubkey,
+    pub referred_donations: u64,
+    pub referred_lamports: u64,
+    pub tokens_earned: u64,
+    pub bump: u8,
+}
+
+// A donor's vesting reward tokens from one campaign, held in their vesting vault
+// for it. A new deposit restarts the schedule for everything not yet claimed
+#[account]
+#[derive(InitSpace)]
+pub struct RewardVesting {
+    pub donor: Pubkey,
+    pub campaign: Pubkey,
+    // Tokens in this schedule, and how many of them have been claimed
+    pub total: u64,
+    pub claimed: u64,
+    pub start_ts: i64,
+    pub cliff_seconds: i64,
+    pub duration_seconds: i64,
+    pub bump: u8,
+}
+
+impl RewardVesting {
+    // Tokens released by `now`: none before the cliff, then linear until the
+    // duration has passed
+    pub fn vested(&self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.start_ts);
+        if elapsed < self.cliff_seconds {
+            return Ok(0);
+        }
+        if elapsed >= self.duration_seconds {
+            return Ok(self.total);
+        }
+        (self.total as u128)
+            .checked_mul(elapsed as u128)
+            .and_then(|value| value.checked_div(self.duration_seconds as u128))
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(error!(DonationError::MathOverflow))
+    }
+}
+
+// A donor's voting power in one campaign, earned by burning its reward tokens
+#[account]
+#[derive(InitSpace)]
+pub struct VotePower {
+    pub campaign: Pubkey,
+    pub donor: Pubkey,
+    pub votes: u64,
+    pub bump: u8,
+}
+
+// Most withdrawal approvers the config can hold
+pub const MAX_APPROVERS: usize = 5;
+
+// Delay between initiating and executing an emergency withdrawal: 48 hours
+pub const EMERGENCY_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
+
+// The pending emergency withdrawal, at [b"emergency"]; at most one at a time
+#[account]
+#[derive(InitSpace)]
+pub struct EmergencyWithdrawal {
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub initiated_ts: i64,
+    pub unlock_ts: i64,
+    pub bump: u8,
+}
+
+// A vault withdrawal waiting for approvals, at [b"proposal", index]
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalProposal {
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    #[max_len(5)]
+    pub approvals: Vec<Pubkey>,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+// Length of the unix day donation streaks are counted in
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+// Campaign names are PDA seeds, which are limited to 32 bytes
+pub const MAX_CAMPAIGN_NAME_LEN: usize = 32;
+
+// Longest description or image uri a campaign can hold
+pub const MAX_CAMPAIGN_URI_LEN: usize = 128;
+
+// A fundraiser with a goal and a donation window, paying out to its beneficiary
+#[account]
+#[derive(InitSpace)]
+pub struct Campaign {
+    pub creator: Pubkey,
+    pub beneficiary: Pubkey,
+    #[max_len(32)]
+    pub name: String,
+    pub goal_lamports: u64,
+    // Donations are accepted from start_ts until just before end_ts
+    pub start_ts: i64,
+    pub end_ts: i64,
+    // Net of platform fees, i.e. what the campaign vault received
+    pub raised: u64,
+    // Number of donations so far; seeds each DonationRecord
+    pub donation_count: u64,
+    // Set by claim_funds; a claimed campaign accepts no more donations
+    pub claimed: bool,
+    // Most lamports one donor may give in total; 0 means no cap
+    pub max_per_donor: u64,
+    // When the first and latest donations landed; 0 until the first one
+    pub first_donation_ts: i64,
+    pub last_donation_ts: i64,
+    // Set by cancel_campaign; donations stop and every one becomes refundable
+    pub cancelled: bool,
+    // Links for frontends, e.g. to a markdown description and a banner image
+    #[max_len(128)]
+    pub description_uri: String,
+    #[max_len(128)]
+    pub image_uri: String,
+    // Number of progress updates so far; seeds each CampaignUpdate
+    pub update_count: u64,
+    // Whether the beneficiary must be a VerifiedBeneficiary to create and claim
+    pub require_verified: bool,
+    // Set once DonationWindowClosed has been emitted
+    pub window_closed: bool,
+    pub bump: u8,
+}
+
+// Admin-approved beneficiary, at [b"verified", beneficiary]; closed on revocation
+#[account]
+#[derive(InitSpace)]
+pub struct VerifiedBeneficiary {
+    pub beneficiary: Pubkey,
+    pub verified_by: Pubkey,
+    pub verified_at: i64,
+    pub bump: u8,
+}
+
+// Longest progress update a beneficiary can post, in bytes
+pub const MAX_UPDATE_LEN: usize = 256;
+
+// One beneficiary progress update, at [b"update", campaign, index]
+#[account]
+#[derive(InitSpace)]
+pub struct CampaignUpdate {
+    pub campaign: Pubkey,
+    pub index: u64,
+    #[max_len(256)]
+    pub content: String,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+// One donor's running total for one campaign
+#[account]
+#[derive(InitSpace)]
+pub struct Contribution {
+    pub campaign: Pubkey,
+    pub donor: Pubkey,
+    pub total_donated: u64,
+}
+
+// Longest donor message a DonationRecord can hold, in bytes
+pub const MAX_DONATION_MEMO_LEN: usize = 128;
+
+// One contribution to a campaign, at seeds [b"donation_record", campaign, index]
+// for index 0..campaign.donation_count
+#[account]
+#[derive(InitSpace)]
+pub struct DonationRecord {
+    pub donor: Pubkey,
+    pub campaign: Pubkey,
+    pub index: u64,
+    // Net amount held for the campaign, which is what a refund returns
+    pub lamports: u64,
+    pub tokens_minted: u64,
+    pub timestamp: i64,
+    pub refunded: bool,
+    // Donor's message for the campaign page; may be empty
+    #[max_len(128)]
+    pub memo: String,
+    pub bump: u8,
+}
+
+// Return value of record_donation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DonationReceipt {
+    // Position among all record_donation donations, from 0
+    pub donation_index: u64,
+    // SpaceX tokens minted to the donor, including any match
+    pub tokens_minted: u64,
+    // Donor's lifetime total after this donation, in lamports
+    pub donor_total: u64,
+}
+
+// Totals across every record_donation call
+#[account]
+#[derive(InitSpace)]
+pub struct GlobalStats {
+    pub total_raised: u64,
+    pub total_donations: u64,
+    pub unique_donors: u64,
+}
+
+// Entries the leaderboard keeps
+pub const LEADERBOARD_SIZE: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct LeaderboardEntry {
+    pub donor: Pubkey,
+    pub total_donated: u64,
+}
+
+// Top donors by lifetime total, highest first
+#[account]
+#[derive(InitSpace)]
+pub struct Leaderboard {
+    #[max_len(10)]
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    // Current top donor, or Pubkey::default() while empty
+    pub fn leader(&self) -> Pubkey {
+        self.entries.first().map_or(Pubkey::default(), |entry| entry.donor)
+    }
+
+    // Move `donor` to its sorted position for `total_donated`, dropping whoever
+    // falls off the end
+    pub fn record(&mut self, donor: Pubkey, total_donated: u64) {
+        self.entries.retain(|entry| entry.donor != donor);
+        let position = self.entries
+            .iter()
+            .position(|entry| entry.total_donated < total_donated)
+            .unwrap_or(self.entries.len());
+        if position < LEADERBOARD_SIZE {
+            self.entries.insert(position, LeaderboardEntry { donor, total_donated });
+            self.entries.truncate(LEADERBOARD_SIZE);
+        }
+    }
+}
+
+// Sponsor SOL that matches record_donation donations; every lamport above its
+// rent reserve is available for matching
+#[account]
+#[derive(InitSpace)]
+pub struct MatchingPool {
+    pub total_funded: u64,
+    pub total_matched: u64,
+    pub bump: u8,
+}
+
+// Pre-funded recurring donation; holds the unspent installments as lamports
+#[account]
+#[derive(InitSpace)]
+pub struct RecurringDonation {
+    pub donor: Pubkey,
+    pub amount: u64,
+    pub interval: i64,
+    pub next_due_ts: i64,
+    pub remaining: u32,
+    pub bump: u8,
+}
+
+// Lifetime totals for one donor
+#[account]
+#[derive(InitSpace)]
+pub struct DonorStats {
+    pub donor: Pubkey,
+    pub total_donated: u64,
+    pub donation_count: u64,
+    pub first_donation_ts: i64,
+    pub last_donation_ts: i64,
+    // Bit i is set once the donor has earned BADGE_NAMES[i]
+    pub badges: u8,
+    // Consecutive unix days with a donation, ending on last_donation_day
+    pub streak_days: u32,
+    pub last_donation_day: i64,
+}
+
+impl DonorStats {
+    // Streak length once the donor donates on unix day `today`: unchanged for a
+    // second donation the same day, one longer the day after, otherwise 1
+    pub fn streak_after(&self, today: i64) -> u32 {
+        if self.donation_count == 0 {
+            1
+        } else if today == self.last_donation_day {
+            self.streak_days.max(1)
+        } else if today == self.last_donation_day + 1 {
+            self.streak_days.saturating_add(1)
+        } else {
+            1
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    /// CHECK: System-owned PDA that receives record_donation SOL
+    #[account(
+        mut,
+        seeds = [b"donation_vault"],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+    /// CHECK: System-owned PDA that receives the platform fee on campaign donations
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MatchingPool::INIT_SPACE,
+        seeds = [b"matching_pool"],
+        bump
+    )]
+    pub matching_pool: Account<'info, MatchingPool>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"spacex_token_mint"],
+        bump,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+    )]
+    pub spacex_mint: Account<'info, Mint>,
+    // Donor badge mints, one per tier in BADGE_NAMES
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"badge_mint", b"bronze".as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+    )]
+    pub bronze_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"badge_mint", b"silver".as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+    )]
+    pub silver_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"badge_mint", b"gold".as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+    )]
+    pub gold_mint: Box<Account<'info, Mint>>,
+    /// CHECK: PDA used as the SpaceX mint authority
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ DonationError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    name: String,
+    goal_lamports: u64,
+    start_ts: i64,
+    end_ts: i64,
+    beneficiary: Pubkey,
+)]
+pub struct CreateCampaign<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Campaign::INIT_SPACE,
+        seeds = [b"campaign", creator.key().as_ref(), name.as_bytes()],
+        bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+    /// CHECK: System-owned escrow PDA holding only this campaign's donations
+    #[account(
+        mut,
+        seeds = [b"vault", campaign.key().as_ref()],
+        bump
+    )]
+    pub campaign_vault: AccountInfo<'info>,
+    // Registry entry for the beneficiary, only required with require_verified
+    #[account(
+        seeds = [b"verified", beneficiary.as_ref()],
+        bump = verified_beneficiary.bump
+    )]
+    pub verified_beneficiary: Option<Account<'info, VerifiedBeneficiary>>,
+    // The campaign's own reward token, minted to its donors
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"reward_mint", campaign.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+    )]
+    pub reward_mint: Box<Account<'info, Mint>>,
+    /// CHECK: PDA used as the reward mint authority
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct VerifyBeneficiary<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ DonationError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + VerifiedBeneficiary::INIT_SPACE,
+        seeds = [b"verified", beneficiary.as_ref()],
+        bump
+    )]
+    pub verified_beneficiary: Account<'info, VerifiedBeneficiary>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeVerification<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ DonationError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"verified", verified_beneficiary.beneficiary.as_ref()],
+        bump = verified_beneficiary.bump
+    )]
+    pub verified_beneficiary: Account<'info, VerifiedBeneficiary>,
+}
+
+#[derive(Accounts)]
+pub struct RecordDonation<'info> {
+    // Keypair wallet or a system-owned PDA signing through invoke_signed, see
+    // day2/donate_cpi
+    #[account(mut)]
+    pub donor: Signer<'info>,
+    // Pays rent for every account created here; usually the donor itself
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: This is the PDA that will receive the donations
+    #[account(
+        mut,
+        seeds = [b"donation_vault"],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+    // Donor's profile - created on their first donation
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + DonorStats::INIT_SPACE,
+        seeds = [b"donor", donor.key().as_ref()],
+        bump
+    )]
+    pub donor_stats: Account<'info, DonorStats>,
+    // Program-wide totals - created on the first donation
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + GlobalStats::INIT_SPACE,
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    // Top donors - created on the first donation
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Leaderboard::INIT_SPACE,
+        seeds = [b"leaderboard"],
+        bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+    #[account(
+        mut,
+        seeds = [b"matching_pool"],
+        bump = matching_pool.bump
+    )]
+    pub matching_pool: Account<'info, MatchingPool>,
+    // SpaceX reward mint, created by initialize
+    #[account(
+        mut,
+        seeds = [b"spacex_token_mint"],
+        bump
+    )]
+    pub spacex_mint: Account<'info, Mint>,
+    // Donor's reward token account - created in the instruction if it doesn't exist
+    /// CHECK: Address is the donor's SpaceX ATA; the token program validates it on mint
+    #[account(
+        mut,
+        address = get_associated_token_address(&donor.key(), &spacex_mint.key())
+    )]
+    pub user_token_account: AccountInfo<'info>,
+    // Badge mints and the donor's ATAs for them, created when a badge is earned
+    #[account(
+        mut,
+        seeds = [b"badge_mint", b"bronze".as_ref()],
+        bump
+    )]
+    pub bronze_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Address is the donor's bronze badge ATA
+    #[account(
+        mut,
+        address = get_associated_token_address(&donor.key(), &bronze_mint.key())
+    )]
+    pub bronze_token_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"badge_mint", b"silver".as_ref()],
+        bump
+    )]
+    pub silver_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Address is the donor's silver badge ATA
+    #[account(
+        mut,
+        address = get_associated_token_address(&donor.key(), &silver_mint.key())
+    )]
+    pub silver_token_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"badge_mint", b"gold".as_ref()],
+        bump
+    )]
+    pub gold_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Address is the donor's gold badge ATA
+    #[account(
+        mut,
+        address = get_associated_token_address(&donor.key(), &gold_mint.key())
+    )]
+    pub gold_token_account: AccountInfo<'info>,
+    /// CHECK: PDA used as the SpaceX mint authority
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    // Pyth SOL/USD price update, only required while USD pricing is on
+    pub price_update: Option<Account<'info, PriceUpdateV2>>,
+    // Receipt NFT accounts, only required when the donation reaches the threshold.
+    // The mint is a fresh keypair that also signs the transaction
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
+    )]
+    pub receipt_mint: Option<Account<'info, Mint>>,
+    /// CHECK: Donor's ATA for receipt_mint, created in the instruction
+    #[account(mut)]
+    pub receipt_token_account: Option<AccountInfo<'info>>,
+    /// CHECK: Created and validated by the Token Metadata program
+    #[account(mut)]
+    pub receipt_metadata: Option<UncheckedAccount<'info>>,
+    /// CHECK: Created and validated by the Token Metadata program
+    #[account(mut)]
+    pub receipt_master_edition: Option<UncheckedAccount<'info>>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+    pub rent: Option<Sysvar<'info, Rent>>,
+    // Referral accounts, only passed when the donor was referred
+    /// CHECK: Any wallet other than the donor; receives the referral reward
+    pub referrer: Option<UncheckedAccount<'info>>,
+    /// CHECK: Referrer's SpaceX ATA, checked in the instruction
+    #[account(mut)]
+    pub referrer_token_account: Option<AccountInfo<'info>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReferralStats::INIT_SPACE,
+        seeds = [
+            b"referral",
+            referrer.as_ref().map(|referrer| referrer.key()).unwrap_or_default().as_ref(),
+        ],
+        bump
+    )]
+    pub referral_stats: Option<Account<'info, ReferralStats>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> RecordDonation<'info> {
+    // Mint the referrer their share of the donor's reward and update their stats.
+    // `public_donor` is what the log shows, Pubkey::default() for anonymous donors
+    fn reward_referrer(
+        &mut self,
+        mint_authority_bump: u8,
+        referral_stats_bump: Option<u8>,
+        lamports: u64,
+        donor_tokens: u64,
+        public_donor: Pubkey,
+    ) -> Result<()> {
+        let (Some(referrer), Some(referrer_token_account), Some(stats), Some(stats_bump)) = (
+            self.referrer.as_ref(),
+            self.referrer_token_account.as_ref(),
+            self.referral_stats.as_mut(),
+            referral_stats_bump,
+        ) else {
+            return err!(DonationError::MissingReferralAccounts);
+        };
+        require!(referrer.key() != self.donor.key(), DonationError::SelfReferral);
+        require!(
+            referrer_token_account.key()
+                == get_associated_token_address(&referrer.key(), &self.spacex_mint.key()),
+            DonationError::MissingReferralAccounts
+        );
+
+        let tokens = (donor_tokens as u128)
+            .checked_mul(self.config.referral_bps as u128)
+            .map(|value| value / 10_000)
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(DonationError::MathOverflow)?;
+
+        stats.referrer = referrer.key();
+        stats.referred_donations = stats.referred_donations
+            .checked_add(1)
+            .ok_or(DonationError::MathOverflow)?;
+        stats.referred_lamports = stats.referred_lamports
+            .checked_add(lamports)
+            .ok_or(DonationError::MathOverflow)?;
+        stats.tokens_earned = stats.tokens_earned
+            .checked_add(tokens)
+            .ok_or(DonationError::MathOverflow)?;
+        stats.bump = stats_bump;
+
+        if tokens > 0 {
+            // The referrer does not sign, so the payer covers their ATA
+            associated_token::create_idempotent(CpiContext::new(
+                self.associated_token_program.to_account_info(),
+                Create {
+                    payer: self.payer.to_account_info(),
+                    associated_token: referrer_token_account.to_account_info(),
+                    authority: referrer.to_account_info(),
+                    mint: self.spacex_mint.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                },
+            ))?;
+            mint_reward(
+                &self.token_program,
+                &self.spacex_mint,
+                referrer_token_account,
+                &self.mint_authority,
+                mint_authority_bump,
+                tokens,
+            )?;
+        }
+
+        msg!(
+            "REFERRAL_EVENT: referrer={}, donor={}, lamports={}, tokens={}",
+            referrer.key(),
+            public_donor,
+            lamports,
+            tokens
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetPaymentMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ DonationError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mint::token_program = payment_token_program)]
+    pub payment_mint: InterfaceAccount<'info, PaymentMint>,
+    // Program-owned account collecting SPL donations, one per payment mint
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"payment_vault", payment_mint.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = config,
+        token::token_program = payment_token_program,
+    )]
+    pub payment_vault: InterfaceAccount<'info, PaymentTokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub payment_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct FundMatchingPool<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"matching_pool"],
+        bump = matching_pool.bump
+    )]
+    pub matching_pool: Account<'info, MatchingPool>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordSplDonation<'info> {
+    #[account(mut)]
+    pub donor: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(address = config.payment_mint @ DonationError::InvalidPaymentMint)]
+    pub payment_mint: InterfaceAccount<'info, PaymentMint>,
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = donor,
+        token::token_program = payment_token_program,
+    )]
+    pub donor_payment_account: InterfaceAccount<'info, PaymentTokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"payment_vault", payment_mint.key().as_ref()],
+        bump
+    )]
+    pub payment_vault: InterfaceAccount<'info, PaymentTokenAccount>,
+    // SpaceX reward mint, created by initialize
+    #[account(
+        mut,
+        seeds = [b"spacex_token_mint"],
+        bump
+    )]
+    pub spacex_mint: Account<'info, Mint>,
+    // Donor's reward token account - created in the instruction if it doesn't exist
+    /// CHECK: Address is the donor's SpaceX ATA; the token program validates it on mint
+    #[account(
+        mut,
+        address = get_associated_token_address(&donor.key(), &spacex_mint.key())
+    )]
+    pub user_token_account: AccountInfo<'info>,
+    /// CHECK: PDA used as the SpaceX mint authority
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub payment_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct CreateRecurring<'info> {
+    #[account(mut)]
+    pub donor: Signer<'info>,
+    #[account(
+        init,
+        payer = donor,
+        space = 8 + RecurringDonation::INIT_SPACE,
+        seeds = [b"recurring", donor.key().as_ref()],
+        bump
+    )]
+    pub schedule: Account<'info, RecurringDonation>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteRecurring<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    /// CHECK: The schedule's donor, checked by has_one; receives the rewards
+    pub donor: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"recurring", donor.key().as_ref()],
+        bump = schedule.bump,
+        has_one = donor @ DonationError::Unauthorized
+    )]
+    pub schedule: Account<'info, RecurringDonation>,
+    /// CHECK: This is the PDA that will receive the donations
+    #[account(
+        mut,
+        seeds = [b"donation_vault"],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+    // SpaceX reward mint, created by initialize
+    #[account(
+        mut,
+        seeds = [b"spacex_token_mint"],
+        bump
+    )]
+    pub spacex_mint: Account<'info, Mint>,
+    /// CHECK: Address is the donor's SpaceX ATA; the token program validates it on mint
+    #[account(
+        mut,
+        address = get_associated_token_address(&donor.key(), &spacex_mint.key())
+    )]
+    pub user_token_account: AccountInfo<'info>,
+    // Same profile, totals and leaderboard as record_donation
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + DonorStats::INIT_SPACE,
+        seeds = [b"donor", donor.key().as_ref()],
+        bump
+    )]
+    pub donor_stats: Box<Account<'info, DonorStats>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + GlobalStats::INIT_SPACE,
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: Box<Account<'info, GlobalStats>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + Leaderboard::INIT_SPACE,
+        seeds = [b"leaderboard"],
+        bump
+    )]
+    pub leaderboard: Box<Account<'info, Leaderboard>>,
+    // Badge mints and the donor's ATAs for them, created when a badge is earned
+    #[account(
+        mut,
+        seeds = [b"badge_mint", b"bronze".as_ref()],
+        bump
+    )]
+    pub bronze_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Address is the donor's bronze badge ATA
+    #[account(
+        mut,
+        address = get_associated_token_address(&donor.key(), &bronze_mint.key())
+    )]
+    pub bronze_token_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"badge_mint", b"silver".as_ref()],
+        bump
+    )]
+    pub silver_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Address is the donor's silver badge ATA
+    #[account(
+        mut,
+        address = get_associated_token_address(&donor.key(), &silver_mint.key())
+    )]
+    pub silver_token_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"badge_mint", b"gold".as_ref()],
+        bump
+    )]
+    pub gold_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Address is the donor's gold badge ATA
+    #[account(
+        mut,
+        address = get_associated_token_address(&donor.key(), &gold_mint.key())
+    )]
+    pub gold_token_account: AccountInfo<'info>,
+    /// CHECK: PDA used as the SpaceX mint authority
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    // Pyth SOL/USD price update, only required while USD pricing is on
+    pub price_update: Option<Account<'info, PriceUpdateV2>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRecurring<'info> {
+    #[account(mut)]
+    pub donor: Signer<'info>,
+    // Closing returns the rent and every unspent installment to the donor
+    #[account(
+        mut,
+        close = donor,
+        seeds = [b"recurring", donor.key().as_ref()],
+        bump = schedule.bump,
+        has_one = donor @ DonationError::Unauthorized
+    )]
+    pub schedule: Account<'info, RecurringDonation>,
+}
+
+#[derive(Accounts)]
+pub struct DonateToCampaign<'info> {
+    #[account(mut)]
+    pub donor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.creator.as_ref(), campaign.name.as_bytes()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+    /// CHECK: System-owned escrow PDA holding only this campaign's donations
+    #[account(
+        mut,
+        seeds = [b"vault", campaign.key().as_ref()],
+        bump
+    )]
+    pub campaign_vault: AccountInfo<'info>,
+    /// CHECK: System-owned PDA that receives the platform fee
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: AccountInfo<'info>,
+    // Donor's total for this campaign - created on their first donation to it
+    #[account(
+        init_if_needed,
+        payer = donor,
+        space = 8 + Contribution::INIT_SPACE,
+        seeds = [b"contribution", campaign.key().as_ref(), donor.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    #[account(
+        init,
+        payer = donor,
+        space = 8 + DonationRecord::INIT_SPACE,
+        seeds = [
+            b"donation_record",
+            campaign.key().as_ref(),
+            campaign.donation_count.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub donation_record: Account<'info, DonationRecord>,
+    // This campaign's reward mint, created by create_campaign
+    #[account(
+        mut,
+        seeds = [b"reward_mint", campaign.key().as_ref()],
+        bump
+    )]
+    pub reward_mint: Box<Account<'info, Mint>>,
+    // Donor's reward token account - created in the instruction if it doesn't exist
+    /// CHECK: Address is the donor's ATA for the reward mint; the token program validates it on mint
+    #[account(
+        mut,
+        address = get_associated_token_address(&donor.key(), &reward_mint.key())
+    )]
+    pub user_token_account: AccountInfo<'info>,
+    /// CHECK: PDA used as the reward mint authority
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    // Pyth SOL/USD price update, only required while USD pricing is on
+    pub price_update: Option<Account<'info, PriceUpdateV2>>,
+    // Vesting accounts, only required while the config vests rewards
+    #[account(
+        init_if_needed,
+        payer = donor,
+        space = 8 + RewardVesting::INIT_SPACE,
+        seeds = [b"vesting", campaign.key().as_ref(), donor.key().as_ref()],
+        bump
+    )]
+    pub reward_vesting: Option<Box<Account<'info, RewardVesting>>>,
+    #[account(
+        init_if_needed,
+        payer = donor,
+        seeds = [b"vesting_vault", campaign.key().as_ref(), donor.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = mint_authority,
+    )]
+    pub vesting_vault: Option<Box<Account<'info, TokenAccount>>>,
+    // wSOL accounts, only passed when paying in wrapped SOL. `amount` moves from
+    // the donor's account into the temporary one, which is unwrapped and closed
+    #[account(
+        mut,
+        token::authority = donor,
+        constraint = wsol_account.mint == native_mint::ID @ DonationError::NotWrappedSol
+    )]
+    pub wsol_account: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(address = native_mint::ID @ DonationError::NotWrappedSol)]
+    pub native_mint: Option<Box<Account<'info, Mint>>>,
+    #[account(
+        init,
+        payer = donor,
+        seeds = [b"wsol_temp", donor.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = mint_authority,
+    )]
+    pub wsol_temp: Option<Box<Account<'info, TokenAccount>>>,
+    // Receipt NFT accounts, only required when the donation reaches the threshold.
+    // The mint is a fresh keypair that also signs the transaction
+    #[account(
+        init,
+        payer = donor,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
+    )]
+    pub receipt_mint: Option<Box<Account<'info, Mint>>>,
+    /// CHECK: Donor's ATA for receipt_mint, created in the instruction
+    #[account(mut)]
+    pub receipt_token_account: Option<AccountInfo<'info>>,
+    /// CHECK: Created and validated by the Token Metadata program
+    #[account(mut)]
+    pub receipt_metadata: Option<UncheckedAccount<'info>>,
+    /// CHECK: Created and validated by the Token Metadata program
+    #[account(mut)]
+    pub receipt_master_edition: Option<UncheckedAccount<'info>>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+    pub rent: Option<Sysvar<'info, Rent>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> DonateToCampaign<'info> {
+    // Mint `tokens` into the donor's vesting vault and restart their schedule for
+    // the unclaimed balance plus the new tokens
+    fn vest_reward(
+        &mut self,
+        mint_authority_bump: u8,
+        vesting_bump: Option<u8>,
+        tokens: u64,
+        now: i64,
+    ) -> Result<()> {
+        let (Some(vesting), Some(vesting_vault), Some(vesting_bump)) =
+            (self.reward_vesting.as_mut(), self.vesting_vault.as_ref(), vesting_bump)
+        else {
+            return err!(DonationError::MissingVestingAccounts);
+        };
+
+        mint_reward(
+            &self.token_program,
+            &self.reward_mint,
+            &vesting_vault.to_account_info(),
+            &self.mint_authority,
+            mint_authority_bump,
+            tokens,
+        )?;
+
+        let unclaimed = vesting.total
+            .checked_sub(vesting.claimed)
+            .ok_or(DonationError::MathOverflow)?;
+        vesting.donor = self.donor.key();
+        vesting.campaign = self.campaign.key();
+        vesting.total = unclaimed
+            .checked_add(tokens)
+            .ok_or(DonationError::MathOverflow)?;
+        vesting.claimed = 0;
+        vesting.start_ts = now;
+        vesting.cliff_seconds = self.config.vesting_cliff_seconds;
+        vesting.duration_seconds = self.config.vesting_duration_seconds;
+        vesting.bump = vesting_bump;
+
+        msg!(
+            "REWARDS_VESTED_EVENT: donor={}, campaign={}, amount={}, total={}",
+            vesting.donor,
+            vesting.campaign,
+            tokens,
+            vesting.total
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub donor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vesting", reward_vesting.campaign.as_ref(), donor.key().as_ref()],
+        bump = reward_vesting.bump,
+        has_one = donor @ DonationError::Unauthorized
+    )]
+    pub reward_vesting: Account<'info, RewardVesting>,
+    #[account(
+        mut,
+        seeds = [b"vesting_vault", reward_vesting.campaign.as_ref(), donor.key().as_ref()],
+        bump
+    )]
+    pub vesting_vault: Account<'info, TokenAccount>,
+    // Reward mint of the campaign the vesting tokens came from
+    #[account(
+        seeds = [b"reward_mint", reward_vesting.campaign.as_ref()],
+        bump
+    )]
+    pub reward_mint: Account<'info, Mint>,
+    // Donor's reward token account - created in the instruction if it doesn't exist
+    /// CHECK: Address is the donor's ATA for the reward mint; the token program validates it on transfer
+    #[account(
+        mut,
+        address = get_associated_token_address(&donor.key(), &reward_mint.key())
+    )]
+    pub user_token_account: AccountInfo<'info>,
+    /// CHECK: PDA used as the reward mint authority and vesting vault owner
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct ConvertToVotes<'info> {
+    #[account(mut)]
+    pub donor: Signer<'info>,
+    #[account(
+        seeds = [b"campaign", campaign.creator.as_ref(), campaign.name.as_bytes()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+    #[account(
+        mut,
+        seeds = [b"reward_mint", campaign.key().as_ref()],
+        bump
+    )]
+    pub reward_mint: Account<'info, Mint>,
+    // Donor's reward tokens to burn
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = donor
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    // Created on the donor's first conversion for this campaign
+    #[account(
+        init_if_needed,
+        payer = donor,
+        space = 8 + VotePower::INIT_SPACE,
+        seeds = [b"vote_power", campaign.key().as_ref(), donor.key().as_ref()],
+        bump
+    )]
+    pub vote_power: Account<'info, VotePower>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCampaignMetadata<'info> {
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.creator.as_ref(), campaign.name.as_bytes()],
+        bump = campaign.bump,
+        has_one = creator @ DonationError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+}
+
+#[derive(Accounts)]
+pub struct PostUpdate<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.creator.as_ref(), campaign.name.as_bytes()],
+        bump = campaign.bump,
+        has_one = beneficiary @ DonationError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    #[account(
+        init,
+        payer = beneficiary,
+        space = 8 + CampaignUpdate::INIT_SPACE,
+        seeds = [
+            b"update",
+            campaign.key().as_ref(),
+            campaign.update_count.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub campaign_update: Account<'info, CampaignUpdate>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelCampaign<'info> {
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.creator.as_ref(), campaign.name.as_bytes()],
+        bump = campaign.bump,
+        has_one = creator @ DonationError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+}
+
+#[derive(Accounts)]
+pub struct Refund<'info> {
+    #[account(mut)]
+    pub donor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.creator.as_ref(), campaign.name.as_bytes()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+    /// CHECK: System-owned escrow PDA holding only this campaign's donations
+    #[account(
+        mut,
+        seeds = [b"vault", campaign.key().as_ref()],
+        bump
+    )]
+    pub campaign_vault: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"donation_record",
+            campaign.key().as_ref(),
+            donation_record.index.to_le_bytes().as_ref(),
+        ],
+        bump = donation_record.bump,
+        has_one = donor @ DonationError::Unauthorized,
+        has_one = campaign @ DonationError::Unauthorized
+    )]
+    pub donation_record: Account<'info, DonationRecord>,
+    #[account(
+        mut,
+        seeds = [b"contribution", campaign.key().as_ref(), donor.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFunds<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.creator.as_ref(), campaign.name.as_bytes()],
+        bump = campaign.bump,
+        has_one = beneficiary @ DonationError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+    /// CHECK: System-owned escrow PDA holding only this campaign's donations
+    #[account(
+        mut,
+        seeds = [b"vault", campaign.key().as_ref()],
+        bump
+    )]
+    pub campaign_vault: AccountInfo<'info>,
+    // Beneficiary's registry entry, only required with require_verified
+    #[account(
+        seeds = [b"verified", beneficiary.key().as_ref()],
+        bump = verified_beneficiary.bump
+    )]
+    pub verified_beneficiary: Option<Account<'info, VerifiedBeneficiary>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitiateEmergencyWithdraw<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ DonationError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + EmergencyWithdrawal::INIT_SPACE,
+        seeds = [b"emergency"],
+        bump
+    )]
+    pub emergency_withdrawal: Account<'info, EmergencyWithdrawal>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteEmergencyWithdraw<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ DonationError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    // Closed once executed, so a new emergency withdrawal can be initiated
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"emergency"],
+        bump = emergency_withdrawal.bump
+    )]
+    pub emergency_withdrawal: Account<'info, EmergencyWithdrawal>,
+    /// CHECK: The donation vault PDA; only its seeds matter
+    #[account(
+        mut,
+        seeds = [b"donation_vault"],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+    /// CHECK: Receives the SOL; must match the pending withdrawal
+    #[account(
+        mut,
+        address = emergency_withdrawal.destination
+    )]
+    pub destination: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelEmergencyWithdraw<'info> {
+    // The admin or one of config.approvers, checked in the instruction
+    pub canceller: Signer<'info>,
+    /// CHECK: Receives the closed account's rent; must be the config admin
+    #[account(
+        mut,
+        address = config.admin @ DonationError::Unauthorized
+    )]
+    pub admin: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"emergency"],
+        bump = emergency_withdrawal.bump
+    )]
+    pub emergency_withdrawal: Account<'info, EmergencyWithdrawal>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeWithdrawal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + WithdrawalProposal::INIT_SPACE,
+        seeds = [b"proposal", config.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveWithdrawal<'info> {
+    pub approver: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.index.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.index.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+    /// CHECK: The donation vault PDA; only its seeds matter
+    #[account(
+        mut,
+        seeds = [b"donation_vault"],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+    /// CHECK: Receives the SOL; must match the proposal
+    #[account(
+        mut,
+        address = proposal.destination
+    )]
+    pub destination: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ DonationError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    /// CHECK: The platform fee vault PDA; only its seeds matter
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct DonationReceived {
+    pub donor: Pubkey,
+    // Total the donor sent, split into the platform fee and the net amount
+    pub lamports: u64,
+    pub fee_lamports: u64,
+    pub net_lamports: u64,
+    // Includes bonus_tokens, the early-bird or streak share
+    pub tokens_minted: u64,
+    pub bonus_tokens: u64,
+    pub campaign: Pubkey,
+    // Whether the donor paid from a wSOL account rather than native SOL
+    pub paid_in_wsol: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FirstDonationReceived {
+    pub campaign: Pubkey,
+    pub donor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DonationWindowClosed {
+    pub campaign: Pubkey,
+    pub first_donation_ts: i64,
+    pub last_donation_ts: i64,
+    pub donation_count: u64,
+    pub raised: u64,
+}
+
+#[event]
+pub struct DonationMatched {
+    pub donor: Pubkey,
+    pub matched_lamports: u64,
+    pub tokens_minted: u64,
+    pub pool_remaining: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LeaderChanged {
+    pub previous_leader: Pubkey,
+    pub new_leader: Pubkey,
+    pub total_donated: u64,
+}
+
+#[event]
+pub struct SplDonationReceived {
+    pub donor: Pubkey,
+    pub payment_mint: Pubkey,
+    pub amount: u64,
+    pub tokens_minted: u64,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum DonationError {
+    #[msg("Donation amount must be greater than zero")]
+    ZeroDonation,
+    #[msg("Donation is below the minimum amount")]
+    BelowMinimum,
+    #[msg("Only the admin can perform this action")]
+    Unauthorized,
+    #[msg("The vault does not hold this many lamports")]
+    InsufficientVaultBalance,
+    #[msg("This withdrawal would leave the vault below its rent-exempt minimum")]
+    WouldBreachRentExemption,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Campaign name must be 1-32 bytes")]
+    InvalidCampaignName,
+    #[msg("Campaign description and image uris must be at most 128 bytes")]
+    CampaignUriTooLong,
+    #[msg("Updates must be 1-256 bytes")]
+    InvalidUpdate,
+    #[msg("The beneficiary is not in the verified registry")]
+    BeneficiaryNotVerified,
+    #[msg("Campaign goal must be greater than zero")]
+    InvalidGoal,
+    #[msg("Campaign must end in the future and after it starts")]
+    InvalidDeadline,
+    #[msg("This campaign is no longer accepting donations")]
+    CampaignClosed,
+    #[msg("This campaign's donation window is not open")]
+    CampaignNotActive,
+    #[msg("This campaign has already ended")]
+    CampaignEnded,
+    #[msg("This campaign was cancelled, so its donations can only be refunded")]
+    CampaignCancelled,
+    #[msg("This campaign has not reached its end yet")]
+    CampaignStillActive,
+    #[msg("This campaign reached its goal, so donations are not refundable")]
+    GoalReached,
+    #[msg("This donation has already been refunded")]
+    AlreadyRefunded,
+    #[msg("This campaign has not reached its goal")]
+    GoalNotReached,
+    #[msg("This campaign's funds have already been claimed")]
+    AlreadyClaimed,
+    #[msg("Fee basis points must be at most 1000")]
+    InvalidFee,
+    #[msg("Reward tiers must be at most 4, strictly increasing, and at least 1x")]
+    InvalidRewardTiers,
+    #[msg("Rate numerator and denominator must be greater than zero")]
+    InvalidRate,
+    #[msg("This token is not the configured payment mint")]
+    InvalidPaymentMint,
+    #[msg("Recurring interval and installments must be greater than zero")]
+    InvalidSchedule,
+    #[msg("Every installment of this schedule has been executed")]
+    ScheduleComplete,
+    #[msg("The next installment is not due yet")]
+    InstallmentNotDue,
+    #[msg("The donor must be a system-owned account without data")]
+    UnsupportedDonorAccount,
+    #[msg("Approvers must be at most 5 distinct keys with a threshold of 1 to their count")]
+    InvalidApprovers,
+    #[msg("Only a withdrawal approver can perform this action")]
+    NotAnApprover,
+    #[msg("This proposal does not have enough approvals")]
+    NotEnoughApprovals,
+    #[msg("This approver has already approved the proposal")]
+    AlreadyApproved,
+    #[msg("This proposal has already been executed")]
+    ProposalExecuted,
+    #[msg("The emergency withdrawal timelock has not elapsed yet")]
+    TimelockActive,
+    #[msg("Enabled badge thresholds must increase from bronze to gold")]
+    InvalidBadgeThresholds,
+    #[msg("Early-bird multiplier must be 0 or at least 10000, with goal bps at most 10000 and non-negative seconds")]
+    InvalidEarlyBird,
+    #[msg("Vesting cliff and duration must be non-negative, with the cliff within the duration")]
+    InvalidVesting,
+    #[msg("Vesting accounts are required while rewards vest")]
+    MissingVestingAccounts,
+    #[msg("No vested reward tokens are claimable yet")]
+    NothingToClaim,
+    #[msg("Referral share must be at most 5000 basis points")]
+    InvalidReferralBps,
+    #[msg("Referrer, its SpaceX ATA and referral stats must be passed together")]
+    MissingReferralAccounts,
+    #[msg("Donors cannot refer themselves")]
+    SelfReferral,
+    #[msg("USD pricing needs a positive max price age and confidence of at most 10000 bps")]
+    InvalidUsdPricing,
+    #[msg("A Pyth price update account is required while USD pricing is on")]
+    MissingPriceUpdate,
+    #[msg("The SOL/USD price is not usable")]
+    InvalidPrice,
+    #[msg("The SOL/USD price confidence interval is too wide")]
+    PriceTooUncertain,
+    #[msg("Streak bonus must be at most 10000 basis points")]
+    InvalidStreakBonus,
+    #[msg("Donations are paused")]
+    DonationsPaused,
+    #[msg("This donation would exceed the campaign's per-donor cap")]
+    DonorCapExceeded,
+    #[msg("Donation memo must be at most 128 bytes")]
+    MemoTooLong,
+    #[msg("The wSOL account must hold wrapped SOL")]
+    NotWrappedSol,
+    #[msg("The wSOL account holds less than the donation amount")]
+    InsufficientWrappedSol,
+    #[msg("Amount to convert to votes must be greater than zero")]
+    ZeroVoteAmount,
+    #[msg("Receipt uri must be at most 96 bytes")]
+    ReceiptUriTooLong,
+    #[msg("Receipt NFT accounts are required for donations above the threshold")]
+    MissingReceiptAccounts,
+}
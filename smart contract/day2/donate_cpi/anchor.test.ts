@@ -0,0 +1,94 @@
+// Solana Playground tests for donation_treasury; run with `test` after `build`
+// and `deploy`. donation_events must already be deployed at DONATION_PROGRAM_ID
+// and initialized, e.g. by running its own tests first.
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+
+const LAMPORTS = web3.LAMPORTS_PER_SOL;
+const DONATION_PROGRAM_ID = new web3.PublicKey(
+  "HPHXtE7dhKP8R1iANQeTZiSFpYcpzmqjBz1CTTunfj4K"
+);
+
+const donationPda = (...seeds: (Buffer | Uint8Array)[]) =>
+  web3.PublicKey.findProgramAddressSync(seeds, DONATION_PROGRAM_ID)[0];
+
+const treasuryPda = web3.PublicKey.findProgramAddressSync(
+  [Buffer.from("treasury"), pg.wallet.publicKey.toBuffer()],
+  pg.PROGRAM_ID
+)[0];
+const vaultPda = donationPda(Buffer.from("donation_vault"));
+
+const writable = (pubkey: web3.PublicKey) => ({ pubkey, isSigner: false, isWritable: true });
+const readonly = (pubkey: web3.PublicKey) => ({ pubkey, isSigner: false, isWritable: false });
+
+// record_donation's accounts in order, passed to donate as remaining accounts.
+// The optional price, receipt and referral accounts are left out, which Anchor
+// reads from the donation program's own id in their place
+const recordDonationAccounts = (donor: web3.PublicKey) => {
+  const spacexMint = donationPda(Buffer.from("spacex_token_mint"));
+  const badge = (tier: string) => {
+    const mint = donationPda(Buffer.from("badge_mint"), Buffer.from(tier));
+    return [writable(mint), writable(getAssociatedTokenAddressSync(mint, donor, true))];
+  };
+  return [
+    writable(donor),
+    { pubkey: pg.wallet.publicKey, isSigner: true, isWritable: true },
+    writable(vaultPda),
+    writable(donationPda(Buffer.from("donor"), donor.toBuffer())),
+    writable(donationPda(Buffer.from("global_stats"))),
+    writable(donationPda(Buffer.from("leaderboard"))),
+    writable(donationPda(Buffer.from("matching_pool"))),
+    writable(spacexMint),
+    writable(getAssociatedTokenAddressSync(spacexMint, donor, true)),
+    ...badge("bronze"),
+    ...badge("silver"),
+    ...badge("gold"),
+    readonly(donationPda(Buffer.from("mint_authority"))),
+    readonly(donationPda(Buffer.from("config"))),
+    // price_update, the six receipt accounts and the three referral accounts
+    ...Array.from({ length: 10 }, () => readonly(DONATION_PROGRAM_ID)),
+    readonly(web3.SystemProgram.programId),
+    readonly(TOKEN_PROGRAM_ID),
+    readonly(ASSOCIATED_TOKEN_PROGRAM_ID),
+  ];
+};
+
+describe("donation_treasury", () => {
+  it("donates from a system-owned treasury PDA", async () => {
+    // A plain transfer is all it takes to fund the treasury
+    await pg.program.provider.sendAndConfirm(
+      new web3.Transaction().add(
+        web3.SystemProgram.transfer({
+          fromPubkey: pg.wallet.publicKey,
+          toPubkey: treasuryPda,
+          lamports: 0.05 * LAMPORTS,
+        })
+      )
+    );
+
+    const amount = 0.01 * LAMPORTS;
+    const treasuryBefore = await pg.connection.getBalance(treasuryPda);
+    const vaultBefore = await pg.connection.getBalance(vaultPda);
+    await pg.program.methods
+      .donate(new BN(amount), false)
+      .accounts({
+        authority: pg.wallet.publicKey,
+        treasury: treasuryPda,
+        donationProgram: DONATION_PROGRAM_ID,
+      })
+      .remainingAccounts(recordDonationAccounts(treasuryPda))
+      .rpc();
+
+    // The authority paid every rent, so only the donation left the treasury
+    assert.equal(await pg.connection.getBalance(treasuryPda), treasuryBefore - amount);
+    // The matching pool may add to the vault, never take from it
+    assert((await pg.connection.getBalance(vaultPda)) >= vaultBefore + amount);
+    const donorStats = await pg.connection.getAccountInfo(
+      donationPda(Buffer.from("donor"), treasuryPda.toBuffer())
+    );
+    assert(donorStats.owner.equals(DONATION_PROGRAM_ID));
+  });
+});
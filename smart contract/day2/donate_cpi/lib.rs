@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::InstructionData;
+// The donation program is pulled in as a crate with its `cpi` feature enabled:
+//   donation_events = { path = "../donate", features = ["cpi"] }
+// That feature generates `donation_events::instruction::*`, used here to encode
+// the record_donation call the treasury signs.
+use donation_events::program::DonationEvents;
+
+declare_id!("11111111111111111111111111111111"); // Playground will replace this
+
+#[program]
+pub mod donation_treasury {
+    use super::*;
+
+    // Donate from the authority's treasury through record_donation. The treasury
+    // is a system-owned PDA, like a Squads vault, so it signs with its seeds while
+    // the authority pays for any accounts record_donation creates.
+    // remaining_accounts are record_donation's accounts in order
+    pub fn donate(ctx: Context<Donate>, amount: u64, anonymous: bool) -> Result<()> {
+        let treasury = ctx.accounts.treasury.key();
+        let accounts = ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == treasury,
+                is_writable: account.is_writable,
+            })
+            .collect();
+        let instruction = Instruction {
+            program_id: ctx.accounts.donation_program.key(),
+            accounts,
+            data: donation_events::instruction::RecordDonation { amount, anonymous }.data(),
+        };
+
+        let authority = ctx.accounts.authority.key();
+        let seeds = &[b"treasury".as_ref(), authority.as_ref(), &[ctx.bumps.treasury]];
+        invoke_signed(&instruction, ctx.remaining_accounts, &[&seeds[..]])?;
+
+        msg!("Treasury {} donated {} lamports", treasury, amount);
+        Ok(())
+    }
+}
+
+// Context for donating from a treasury PDA
+#[derive(Accounts)]
+pub struct Donate<'info> {
+    // Controls the treasury and pays rent for the donation's accounts
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    // System-owned PDA holding the treasury's SOL; funded by plain transfers
+    #[account(
+        mut,
+        seeds = [b"treasury", authority.key().as_ref()],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+    // The donation program itself, checked against donation_events::ID
+    pub donation_program: Program<'info, DonationEvents>,
+}
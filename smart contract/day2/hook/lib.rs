@@ -0,0 +1,326 @@
+use anchor_lang::prelude::*;
+// Besides anchor-lang (with the `interface-instructions` feature) and anchor-spl,
+// this program needs:
+//   spl-tlv-account-resolution, spl-transfer-hook-interface
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TransferChecked};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        transfer_hook::{TransferHook as TransferHookExtension, TransferHookAccount},
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::{Account as TokenAccountState, Mint as MintState},
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+declare_id!("11111111111111111111111111111111"); // Playground will replace this
+
+// Token-2022 calls this program on every transfer of a mint created with
+// spl_token_demo::create_transfer_hook_mint. The hook charges a tax of `tax_bps`
+// of the transferred amount, paid in a separate legacy-program token (e.g. wSOL)
+// that the sender has approved the `delegate` PDA to spend. The tax cannot be
+// taken in the hooked token itself: Token-2022 -> hook -> Token-2022 is reentrant.
+#[program]
+pub mod transfer_hook_tax {
+    use super::*;
+
+    // Write the extra accounts Token-2022 must pass to the hook, and the tax config.
+    // Only the mint's transfer-hook authority can do this, and it becomes the tax
+    // authority
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+        tax_bps: u16,
+    ) -> Result<()> {
+        require!(tax_bps <= MAX_TAX_BPS, HookError::InvalidTax);
+        assert_hook_authority(
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.payer.key(),
+        )?;
+
+        let tax_config = &mut ctx.accounts.tax_config;
+        tax_config.authority = ctx.accounts.payer.key();
+        tax_config.mint = ctx.accounts.mint.key();
+        tax_config.tax_mint = ctx.accounts.tax_mint.key();
+        tax_config.treasury = ctx.accounts.treasury.key();
+        tax_config.tax_bps = tax_bps;
+        tax_config.bump = ctx.bumps.tax_config;
+
+        let metas = extra_account_metas(&tax_config.tax_mint, &tax_config.treasury)?;
+        let mut data = ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas)?;
+
+        msg!(
+            "Transfer hook registered for {} with a {} bps tax",
+            tax_config.mint,
+            tax_bps
+        );
+
+        Ok(())
+    }
+
+    // Authority: change the tax rate
+    pub fn set_tax_bps(ctx: Context<SetTaxBps>, tax_bps: u16) -> Result<()> {
+        require!(tax_bps <= MAX_TAX_BPS, HookError::InvalidTax);
+        ctx.accounts.tax_config.tax_bps = tax_bps;
+        msg!("Transfer tax set to {} bps", tax_bps);
+        Ok(())
+    }
+
+    // Invoked by Token-2022 during transfer_checked; not callable on its own
+    #[interface(spl_transfer_hook_interface::execute)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        assert_is_transferring(&ctx.accounts.source_token.to_account_info())?;
+
+        let tax = (amount as u128)
+            .checked_mul(ctx.accounts.tax_config.tax_bps as u128)
+            .map(|value| value / 10_000)
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(HookError::MathOverflow)?;
+        if tax == 0 {
+            return Ok(());
+        }
+
+        // The delegate PDA spends the sender's tax tokens on their behalf
+        let seeds = &[b"delegate".as_ref(), &[ctx.bumps.delegate]];
+        let signer_seeds = &[&seeds[..]];
+        token::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.sender_tax_account.to_account_info(),
+                    mint: ctx.accounts.tax_mint.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                    authority: ctx.accounts.delegate.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            tax,
+            ctx.accounts.tax_mint.decimals,
+        )?;
+
+        msg!(
+            "TRANSFER_TAX_EVENT: owner={}, amount={}, tax={}",
+            ctx.accounts.owner.key(),
+            amount,
+            tax
+        );
+
+        Ok(())
+    }
+}
+
+// Extra accounts appended after the five standard execute accounts
+// (source 0, mint 1, destination 2, owner 3, extra_account_meta_list 4)
+fn extra_account_metas(tax_mint: &Pubkey, treasury: &Pubkey) -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        // 5: tax_config PDA
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"tax_config".to_vec() },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            false,
+        )?,
+        // 6: tax_mint
+        ExtraAccountMeta::new_with_pubkey(tax_mint, false, false)?,
+        // 7: legacy token program, which owns the tax mint
+        ExtraAccountMeta::new_with_pubkey(&Token::id(), false, false)?,
+        // 8: associated token program, used to derive the sender's tax account
+        ExtraAccountMeta::new_with_pubkey(&AssociatedToken::id(), false, false)?,
+        // 9: delegate PDA
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal { bytes: b"delegate".to_vec() }],
+            false,
+            false,
+        )?,
+        // 10: owner's associated token account for the tax mint
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            8,
+            &[
+                Seed::AccountKey { index: 3 },
+                Seed::AccountKey { index: 7 },
+                Seed::AccountKey { index: 6 },
+            ],
+            false,
+            true,
+        )?,
+        // 11: treasury
+        ExtraAccountMeta::new_with_pubkey(treasury, false, true)?,
+    ])
+}
+
+// Number of entries returned by extra_account_metas
+pub const EXTRA_ACCOUNT_COUNT: usize = 7;
+
+// Highest tax a mint can be configured with: 10%
+pub const MAX_TAX_BPS: u16 = 1_000;
+
+// Require `signer` to be the mint's transfer-hook authority, and the hook to point
+// at this program
+fn assert_hook_authority(mint: &AccountInfo, signer: &Pubkey) -> Result<()> {
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let extension = state.get_extension::<TransferHookExtension>()?;
+    require!(
+        Option::<Pubkey>::from(extension.program_id) == Some(crate::ID),
+        HookError::WrongHookProgram
+    );
+    require!(
+        Option::<Pubkey>::from(extension.authority) == Some(*signer),
+        HookError::Unauthorized
+    );
+    Ok(())
+}
+
+// Reject direct calls: the source account's `transferring` flag is only set by
+// Token-2022 while it is executing a transfer
+fn assert_is_transferring(source_token: &AccountInfo) -> Result<()> {
+    let data = source_token.try_borrow_data()?;
+    let account = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+    let extension = account.get_extension::<TransferHookAccount>()?;
+    require!(bool::from(extension.transferring), HookError::NotTransferring);
+    Ok(())
+}
+
+// Tax settings per hooked mint
+#[account]
+#[derive(InitSpace)]
+pub struct TaxConfig {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    // Legacy-program token the tax is paid in, and where it goes
+    pub tax_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub tax_bps: u16,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    // Must be the mint's transfer-hook authority
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Written with the ExtraAccountMetaList TLV layout in the instruction
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(EXTRA_ACCOUNT_COUNT)?,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TaxConfig::INIT_SPACE,
+        seeds = [b"tax_config", mint.key().as_ref()],
+        bump,
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+
+    // The Token-2022 mint whose transfer hook points at this program
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // Token the tax is paid in; must belong to the legacy token program
+    #[account(mint::token_program = token_program)]
+    pub tax_mint: InterfaceAccount<'info, Mint>,
+
+    // Token account receiving the tax
+    #[account(
+        token::mint = tax_mint,
+        token::token_program = token_program,
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetTaxBps<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tax_config", tax_config.mint.as_ref()],
+        bump = tax_config.bump,
+        has_one = authority @ HookError::Unauthorized,
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+}
+
+// Account order is fixed by the transfer hook interface and extra_account_metas
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Source owner or delegate, as passed by Token-2022
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: ExtraAccountMetaList written by initialize_extra_account_meta_list
+    #[account(
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"tax_config", mint.key().as_ref()],
+        bump = tax_config.bump,
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+
+    #[account(address = tax_config.tax_mint)]
+    pub tax_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: PDA the sender approves to spend their tax tokens
+    #[account(
+        seeds = [b"delegate"],
+        bump,
+    )]
+    pub delegate: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = tax_mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub sender_tax_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = tax_config.treasury,
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+}
+
+#[error_code]
+pub enum HookError {
+    #[msg("Tax must be at most 1000 basis points")]
+    InvalidTax,
+    #[msg("Only the tax or transfer-hook authority can perform this action")]
+    Unauthorized,
+    #[msg("The mint's transfer hook does not point at this program")]
+    WrongHookProgram,
+    #[msg("The transfer hook can only be called during a transfer")]
+    NotTransferring,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,389 @@
+// Solana Playground tests for spl_token_demo; run with `test` after `build` and
+// `deploy`. Every test creates its own mints, so runs do not interfere.
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccount,
+  createAssociatedTokenAccountIdempotentInstruction,
+  createMultisig,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+
+const pda = (...seeds: (Buffer | Uint8Array)[]) =>
+  web3.PublicKey.findProgramAddressSync(seeds, pg.PROGRAM_ID)[0];
+
+const mintAuthority = pda(Buffer.from("mint_authority"));
+const mintPda = (name: string) => pda(Buffer.from("mint"), Buffer.from(name));
+const configPda = (mint: web3.PublicKey) => pda(Buffer.from("config"), mint.toBuffer());
+const mintStatsPda = (mint: web3.PublicKey) =>
+  pda(Buffer.from("mint_stats"), mint.toBuffer());
+
+// Mint names are seeds, so each test run needs fresh ones
+let nameCounter = 0;
+const uniqueName = (prefix: string) => `${prefix}-${Date.now() % 1e9}-${nameCounter++}`;
+
+const expectError = async (tx: Promise<unknown>, code: string | RegExp) => {
+  try {
+    await tx;
+  } catch (err) {
+    if (typeof code === "string") {
+      assert.include(err.toString(), code);
+    } else {
+      assert.match(err.toString(), code);
+    }
+    return;
+  }
+  assert.fail(`expected ${code}`);
+};
+
+// A mint from create_mint with no faucet limits beyond its supply cap
+const createMint = async (tokenProgram: web3.PublicKey, decimals = 6) => {
+  const name = uniqueName("mint");
+  const tokenMint = mintPda(name);
+  await pg.program.methods
+    .createMint(name, decimals, new BN(1_000_000_000_000), new BN(1e12), new BN(1e12))
+    .accounts({
+      admin: pg.wallet.publicKey,
+      config: configPda(tokenMint),
+      mintStats: mintStatsPda(tokenMint),
+      tokenMint,
+      mintAuthority,
+      tokenProgram,
+    })
+    .rpc();
+  return tokenMint;
+};
+
+const ata = (mint: web3.PublicKey, owner: web3.PublicKey, tokenProgram: web3.PublicKey) =>
+  getAssociatedTokenAddressSync(mint, owner, true, tokenProgram);
+
+const balance = async (
+  mint: web3.PublicKey,
+  owner: web3.PublicKey,
+  tokenProgram: web3.PublicKey
+) => {
+  const account = await getAccount(
+    pg.connection,
+    ata(mint, owner, tokenProgram),
+    "confirmed",
+    tokenProgram
+  );
+  return Number(account.amount);
+};
+
+// mint_tokens to the Playground wallet, without memo, allowlist, gate or referrer
+const mintTokens = async (
+  tokenMint: web3.PublicKey,
+  amount: number,
+  tokenProgram: web3.PublicKey
+) => {
+  const user = pg.wallet.publicKey;
+  const { totalMints } = await pg.program.account.mintStats.fetch(mintStatsPda(tokenMint));
+  return pg.program.methods
+    .mintTokens(new BN(amount), null)
+    .accounts({
+      user,
+      config: configPda(tokenMint),
+      tokenMint,
+      userTokenAccount: ata(tokenMint, user, tokenProgram),
+      mintRecord: pda(Buffer.from("mint_record"), tokenMint.toBuffer(), user.toBuffer()),
+      mintStats: mintStatsPda(tokenMint),
+      mintReceipt: pda(
+        Buffer.from("receipt"),
+        tokenMint.toBuffer(),
+        user.toBuffer(),
+        new BN(totalMints).toArrayLike(Buffer, "le", 8)
+      ),
+      allowlistEntry: null,
+      gateTokenAccount: null,
+      referrer: null,
+      referrerTokenAccount: null,
+      referralStats: null,
+      referrerRecord: null,
+      mintAuthority,
+      memoProgram: null,
+      tokenProgram,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+};
+
+const transferTokens = (
+  tokenMint: web3.PublicKey,
+  recipient: web3.PublicKey,
+  amount: number,
+  tokenProgram: web3.PublicKey
+) =>
+  pg.program.methods
+    .transferTokens(new BN(amount))
+    .accounts({
+      user: pg.wallet.publicKey,
+      recipient,
+      tokenMint,
+      userTokenAccount: ata(tokenMint, pg.wallet.publicKey, tokenProgram),
+      recipientTokenAccount: ata(tokenMint, recipient, tokenProgram),
+      tokenProgram,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+const burnTokens = (tokenMint: web3.PublicKey, amount: number, tokenProgram: web3.PublicKey) =>
+  pg.program.methods
+    .burnTokens(new BN(amount))
+    .accounts({
+      user: pg.wallet.publicKey,
+      tokenMint,
+      userTokenAccount: ata(tokenMint, pg.wallet.publicKey, tokenProgram),
+      tokenProgram,
+    })
+    .rpc();
+
+// Compute units a confirmed transaction consumed, as reported by the validator
+const computeUnits = async (signature: string) => {
+  await pg.connection.confirmTransaction(signature, "confirmed");
+  const tx = await pg.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  return tx.meta.computeUnitsConsumed;
+};
+
+// Creates `owner`'s ATA in a transaction of its own and returns the compute units
+// the ATA program spent on it
+const createAtaDirectly = async (
+  mint: web3.PublicKey,
+  owner: web3.PublicKey,
+  tokenProgram: web3.PublicKey
+) =>
+  computeUnits(
+    await pg.program.provider.sendAndConfirm(
+      new web3.Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(
+          pg.wallet.publicKey,
+          ata(mint, owner, tokenProgram),
+          owner,
+          mint,
+          tokenProgram
+        )
+      )
+    )
+  );
+
+// Allowance for invoking the ATA program from inside an instruction rather than
+// from the transaction: the CPI itself and passing its accounts
+const CPI_OVERHEAD_CU = 5_000;
+
+describe("spl_token_demo", () => {
+  // The token instructions take the token program as an interface, so the same
+  // flow has to work against both programs
+  for (const [label, tokenProgram] of [
+    ["SPL Token", TOKEN_PROGRAM_ID],
+    ["Token-2022", TOKEN_2022_PROGRAM_ID],
+  ] as const) {
+    describe(`with ${label}`, () => {
+      let tokenMint: web3.PublicKey;
+      const recipient = new web3.Keypair().publicKey;
+
+      before(async () => {
+        tokenMint = await createMint(tokenProgram);
+      });
+
+      it("creates the mint under the given token program", async () => {
+        const info = await pg.connection.getAccountInfo(tokenMint);
+        assert(info.owner.equals(tokenProgram));
+      });
+
+      it("mints to the caller", async () => {
+        await mintTokens(tokenMint, 1_000, tokenProgram);
+        assert.equal(await balance(tokenMint, pg.wallet.publicKey, tokenProgram), 1_000);
+      });
+
+      it("transfers to a new recipient", async () => {
+        await transferTokens(tokenMint, recipient, 400, tokenProgram);
+        assert.equal(await balance(tokenMint, pg.wallet.publicKey, tokenProgram), 600);
+        assert.equal(await balance(tokenMint, recipient, tokenProgram), 400);
+      });
+
+      it("burns from the caller", async () => {
+        await burnTokens(tokenMint, 100, tokenProgram);
+        assert.equal(await balance(tokenMint, pg.wallet.publicKey, tokenProgram), 500);
+      });
+
+      it("rejects burning more than the balance", async () => {
+        await expectError(burnTokens(tokenMint, 501, tokenProgram), "InsufficientBalance");
+      });
+    });
+  }
+
+  describe("soulbound mint", () => {
+    let tokenMint: web3.PublicKey;
+
+    before(async () => {
+      const name = uniqueName("soul");
+      tokenMint = mintPda(name);
+      await pg.program.methods
+        .createSoulboundMint(name, 0, new BN(1_000))
+        .accounts({
+          admin: pg.wallet.publicKey,
+          config: configPda(tokenMint),
+          mintStats: mintStatsPda(tokenMint),
+          tokenMint,
+          mintAuthority,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+    });
+
+    it("mints to the holder", async () => {
+      await mintTokens(tokenMint, 10, TOKEN_2022_PROGRAM_ID);
+      assert.equal(await balance(tokenMint, pg.wallet.publicKey, TOKEN_2022_PROGRAM_ID), 10);
+    });
+
+    it("lets the holder burn", async () => {
+      await burnTokens(tokenMint, 4, TOKEN_2022_PROGRAM_ID);
+      assert.equal(await balance(tokenMint, pg.wallet.publicKey, TOKEN_2022_PROGRAM_ID), 6);
+    });
+
+    it("rejects transfers", async () => {
+      // Token-2022 fails the transfer with TokenError::NonTransferable (0x25)
+      await expectError(
+        transferTokens(tokenMint, new web3.Keypair().publicKey, 1, TOKEN_2022_PROGRAM_ID),
+        /0x25|non-?transferable/i
+      );
+      assert.equal(await balance(tokenMint, pg.wallet.publicKey, TOKEN_2022_PROGRAM_ID), 6);
+    });
+  });
+
+  describe("multisig mint authority", () => {
+    // A 2-of-3 multisig over fresh keypairs; the Playground wallet pays for
+    // everything and holds the destination account
+    const signers = [new web3.Keypair(), new web3.Keypair(), new web3.Keypair()];
+    let tokenMint: web3.PublicKey;
+    let multisig: web3.PublicKey;
+    let destination: web3.PublicKey;
+
+    const mintWithMultisig = (amount: number, cosigners: web3.Keypair[]) =>
+      pg.program.methods
+        .mintWithMultisig(new BN(amount))
+        .accounts({
+          config: configPda(tokenMint),
+          tokenMint,
+          mintStats: mintStatsPda(tokenMint),
+          destination,
+          multisig,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(
+          cosigners.map((signer) => ({
+            pubkey: signer.publicKey,
+            isSigner: true,
+            isWritable: false,
+          }))
+        )
+        .signers(cosigners)
+        .rpc();
+
+    before(async () => {
+      tokenMint = await createMint(TOKEN_PROGRAM_ID);
+      multisig = await createMultisig(
+        pg.connection,
+        pg.wallet.keypair,
+        signers.map((signer) => signer.publicKey),
+        2,
+        undefined,
+        { commitment: "confirmed" },
+        TOKEN_PROGRAM_ID
+      );
+      await pg.program.methods
+        .rotateToMultisig()
+        .accounts({
+          admin: pg.wallet.publicKey,
+          config: configPda(tokenMint),
+          tokenMint,
+          multisig,
+          mintAuthority,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      destination = await createAssociatedTokenAccount(
+        pg.connection,
+        pg.wallet.keypair,
+        tokenMint,
+        pg.wallet.publicKey,
+        { commitment: "confirmed" },
+        TOKEN_PROGRAM_ID
+      );
+    });
+
+    it("rejects fewer than M signers", async () => {
+      // The token program refuses the mint with MissingRequiredSignature
+      await expectError(mintWithMultisig(500, signers.slice(0, 1)), /missing required signature/i);
+      assert.equal(await balance(tokenMint, pg.wallet.publicKey, TOKEN_PROGRAM_ID), 0);
+    });
+
+    it("accepts exactly M signers", async () => {
+      await mintWithMultisig(500, [signers[0], signers[2]]);
+      assert.equal(await balance(tokenMint, pg.wallet.publicKey, TOKEN_PROGRAM_ID), 500);
+
+      const stats = await pg.program.account.mintStats.fetch(mintStatsPda(tokenMint));
+      assert.equal(stats.totalMinted.toNumber(), 500);
+      assert.equal(stats.totalMints.toNumber(), 1);
+    });
+
+    it("is stopped by pausing the mint", async () => {
+      const updateConfig = { admin: pg.wallet.publicKey, config: configPda(tokenMint) };
+      await pg.program.methods.pauseMinting().accounts(updateConfig).rpc();
+      try {
+        await expectError(mintWithMultisig(500, signers.slice(0, 2)), "MintingPaused");
+      } finally {
+        await pg.program.methods.unpauseMinting().accounts(updateConfig).rpc();
+      }
+    });
+  });
+
+  // Each path is measured twice, once creating the ATA in the instruction and once
+  // with the ATA created beforehand. With create_idempotent the difference is only
+  // the ATA program's own work; the init_if_needed constraint it replaced also
+  // deserialized and re-validated the new account and read the rent sysvar
+  describe("ATA creation cost", () => {
+    it("mint_tokens adds no more than the ATA program's own cost", async () => {
+      const creatingMint = await createMint(TOKEN_PROGRAM_ID);
+      const creating = await computeUnits(
+        await mintTokens(creatingMint, 1_000, TOKEN_PROGRAM_ID)
+      );
+
+      const existingMint = await createMint(TOKEN_PROGRAM_ID);
+      const ataCost = await createAtaDirectly(
+        existingMint,
+        pg.wallet.publicKey,
+        TOKEN_PROGRAM_ID
+      );
+      const existing = await computeUnits(
+        await mintTokens(existingMint, 1_000, TOKEN_PROGRAM_ID)
+      );
+
+      assert.isAbove(creating, existing);
+      assert.isAtMost(creating - existing, ataCost + CPI_OVERHEAD_CU);
+    });
+
+    it("transfer_tokens adds no more than the ATA program's own cost", async () => {
+      const tokenMint = await createMint(TOKEN_PROGRAM_ID);
+      await mintTokens(tokenMint, 1_000, TOKEN_PROGRAM_ID);
+
+      const creating = await computeUnits(
+        await transferTokens(tokenMint, new web3.Keypair().publicKey, 100, TOKEN_PROGRAM_ID)
+      );
+
+      const recipient = new web3.Keypair().publicKey;
+      const ataCost = await createAtaDirectly(tokenMint, recipient, TOKEN_PROGRAM_ID);
+      const existing = await computeUnits(
+        await transferTokens(tokenMint, recipient, 100, TOKEN_PROGRAM_ID)
+      );
+
+      assert.isAbove(creating, existing);
+      assert.isAtMost(creating - existing, ataCost + CPI_OVERHEAD_CU);
+    });
+  });
+});
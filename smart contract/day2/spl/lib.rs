@@ -1,9 +1,54 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{Transfer, transfer, System};
+use anchor_lang::system_program::{Transfer, transfer, System, create_account, CreateAccount};
 use anchor_lang::solana_program::system_instruction;
-use anchor_lang::solana_program::program::invoke;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, MintTo, InitializeMint};
-use anchor_spl::associated_token::{self, AssociatedToken, Create};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+// token_interface works with both the legacy token program and Token-2022
+use anchor_spl::token_interface::{
+    self, Approve, Burn, CloseAccount, FreezeAccount, Mint, MintTo, Revoke, SetAuthority,
+    SyncNative, ThawAccount, TokenAccount, TokenInterface, TransferChecked,
+};
+use anchor_spl::token::{spl_token::native_mint, Token};
+use anchor_spl::token_2022::spl_token_2022::instruction::{
+    mint_to as mint_to_instruction, AuthorityType,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Multisig;
+use anchor_spl::associated_token::{
+    self, get_associated_token_address_with_program_id, AssociatedToken, Create,
+};
+use anchor_spl::token_2022::{initialize_mint2, InitializeMint2, Token2022};
+use anchor_spl::token_2022::spl_token_2022::{extension::ExtensionType, state::Mint as MintState};
+use anchor_spl::token_2022_extensions::interest_bearing_mint::{
+    interest_bearing_mint_initialize, interest_bearing_mint_update_rate,
+    InterestBearingMintInitialize, InterestBearingMintUpdateRate,
+};
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::Field;
+use anchor_spl::token_2022_extensions::token_metadata::{
+    token_metadata_initialize, token_metadata_update_field, TokenMetadataInitialize,
+    TokenMetadataUpdateField,
+};
+use anchor_spl::token_2022_extensions::transfer_hook::{
+    transfer_hook_initialize, TransferHookInitialize,
+};
+use anchor_spl::token_2022_extensions::non_transferable::{
+    non_transferable_mint_initialize, NonTransferableMintInitialize,
+};
+use anchor_spl::token_2022_extensions::transfer_fee::{
+    harvest_withheld_tokens_to_mint, transfer_fee_initialize,
+    withdraw_withheld_tokens_from_mint, HarvestWithheldTokensToMint, TransferFeeInitialize,
+    WithdrawWithheldTokensFromMint,
+};
+use anchor_spl::memo::{build_memo, BuildMemo, Memo};
+use anchor_spl::metadata::{
+    mpl_token_metadata::{instructions::CreateMetadataAccountV3CpiBuilder, types::DataV2},
+    Metadata,
+};
 
 // Make sure this ID matches the one in your frontend (advancedonate.js)
 declare_id!("A9REH6DTms1Jxzj3csutdn1wpBdCk9yBHNxAdrx4H5K5");
@@ -12,66 +57,4204 @@ declare_id!("A9REH6DTms1Jxzj3csutdn1wpBdCk9yBHNxAdrx4H5K5");
 pub mod spl_token_demo {
     use super::*;
 
-    // Simple function to mint tokens to the user's wallet
-    pub fn mint_tokens(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
-        // Mint tokens to user
-        let cpi_accounts = MintTo {
-            mint: ctx.accounts.token_mint.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
-            authority: ctx.accounts.mint_authority.to_account_info(),
+    // Create a named mint together with its config; the caller becomes its admin
+    pub fn create_mint(
+        ctx: Context<CreateMint>,
+        name: String,
+        decimals: u8,
+        max_supply: u64,
+        max_per_wallet: u64,
+        max_per_tx: u64,
+    ) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= MAX_MINT_NAME_LEN,
+            TokenDemoError::InvalidMintName
+        );
+        require!(decimals <= MAX_DECIMALS, TokenDemoError::InvalidDecimals);
+
+        let config = &mut ctx.accounts.config;
+        config.init(
+            ctx.accounts.admin.key(),
+            ctx.accounts.token_mint.key(),
+            decimals,
+            max_supply,
+            ctx.bumps.config,
+        );
+        config.max_per_wallet = max_per_wallet;
+        config.max_per_tx = max_per_tx;
+
+        msg!(
+            "Mint '{}' ({}) created, decimals={}, admin={}, max_supply={}",
+            name,
+            config.mint,
+            decimals,
+            config.admin,
+            config.max_supply
+        );
+
+        Ok(())
+    }
+
+    // Create a named Token-2022 mint whose permanent delegate is the mint authority PDA
+    pub fn create_clawback_mint(
+        ctx: Context<CreateClawbackMint>,
+        name: String,
+        decimals: u8,
+        max_supply: u64,
+    ) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= MAX_MINT_NAME_LEN,
+            TokenDemoError::InvalidMintName
+        );
+        require!(decimals <= MAX_DECIMALS, TokenDemoError::InvalidDecimals);
+
+        ctx.accounts.config.init(
+            ctx.accounts.admin.key(),
+            ctx.accounts.token_mint.key(),
+            decimals,
+            max_supply,
+            ctx.bumps.config,
+        );
+
+        msg!(
+            "Clawback mint '{}' ({}) created with permanent delegate {}",
+            name,
+            ctx.accounts.token_mint.key(),
+            ctx.accounts.mint_authority.key()
+        );
+
+        Ok(())
+    }
+
+    // Admin: move tokens out of any holder's account using the permanent delegate
+    pub fn clawback(ctx: Context<Clawback>, amount: u64) -> Result<()> {
+        require!(amount > 0, TokenDemoError::InvalidAmount);
+        require!(
+            ctx.accounts.source_token_account.amount >= amount,
+            TokenDemoError::InsufficientBalance
+        );
+
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.source_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.destination_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+
+        msg!(
+            "TOKEN_CLAWBACK_EVENT: from={}, to={}, amount={}",
+            ctx.accounts.source_token_account.owner,
+            ctx.accounts.destination_token_account.key(),
+            amount
+        );
+
+        Ok(())
+    }
+
+    // Create a named soulbound mint: Token-2022 NonTransferable, so holders can
+    // receive (mint) and burn it but never transfer it
+    pub fn create_soulbound_mint(
+        ctx: Context<CreateSoulboundMint>,
+        name: String,
+        decimals: u8,
+        max_supply: u64,
+    ) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= MAX_MINT_NAME_LEN,
+            TokenDemoError::InvalidMintName
+        );
+        require!(decimals <= MAX_DECIMALS, TokenDemoError::InvalidDecimals);
+
+        let mint_seeds = &[b"mint".as_ref(), name.as_bytes(), &[ctx.bumps.token_mint]];
+        create_extension_mint_account(
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[ExtensionType::NonTransferable],
+            &mint_seeds[..],
+        )?;
+
+        non_transferable_mint_initialize(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            NonTransferableMintInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+            },
+        ))?;
+
+        let authority = ctx.accounts.mint_authority.key();
+        initialize_mint2(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                },
+            ),
+            decimals,
+            &authority,
+            Some(&authority),
+        )?;
+
+        ctx.accounts.config.init(
+            ctx.accounts.admin.key(),
+            ctx.accounts.token_mint.key(),
+            decimals,
+            max_supply,
+            ctx.bumps.config,
+        );
+
+        msg!(
+            "Soulbound mint '{}' ({}) created",
+            name,
+            ctx.accounts.token_mint.key()
+        );
+
+        Ok(())
+    }
+
+    // Create a named Token-2022 mint whose transfers invoke `hook_program`, such as
+    // the transfer_hook_tax program; the admin can later repoint the hook
+    pub fn create_transfer_hook_mint(
+        ctx: Context<CreateTransferHookMint>,
+        name: String,
+        decimals: u8,
+        max_supply: u64,
+        hook_program: Pubkey,
+    ) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= MAX_MINT_NAME_LEN,
+            TokenDemoError::InvalidMintName
+        );
+        require!(decimals <= MAX_DECIMALS, TokenDemoError::InvalidDecimals);
+
+        let mint_seeds = &[b"mint".as_ref(), name.as_bytes(), &[ctx.bumps.token_mint]];
+        create_extension_mint_account(
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[ExtensionType::TransferHook],
+            &mint_seeds[..],
+        )?;
+
+        transfer_hook_initialize(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferHookInitialize {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                },
+            ),
+            Some(ctx.accounts.admin.key()),
+            Some(hook_program),
+        )?;
+
+        let authority = ctx.accounts.mint_authority.key();
+        initialize_mint2(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                },
+            ),
+            decimals,
+            &authority,
+            Some(&authority),
+        )?;
+
+        ctx.accounts.config.init(
+            ctx.accounts.admin.key(),
+            ctx.accounts.token_mint.key(),
+            decimals,
+            max_supply,
+            ctx.bumps.config,
+        );
+
+        msg!(
+            "Transfer-hook mint '{}' ({}) created with hook program {}",
+            name,
+            ctx.accounts.token_mint.key(),
+            hook_program
+        );
+
+        Ok(())
+    }
+
+    // Create a named Token-2022 mint whose displayed balances accrue interest at `rate_bps`
+    pub fn create_interest_bearing_mint(
+        ctx: Context<CreateInterestBearingMint>,
+        name: String,
+        decimals: u8,
+        max_supply: u64,
+        rate_bps: i16,
+    ) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= MAX_MINT_NAME_LEN,
+            TokenDemoError::InvalidMintName
+        );
+        require!(decimals <= MAX_DECIMALS, TokenDemoError::InvalidDecimals);
+
+        let mint_seeds = &[b"mint".as_ref(), name.as_bytes(), &[ctx.bumps.token_mint]];
+        create_extension_mint_account(
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[ExtensionType::InterestBearingConfig],
+            &mint_seeds[..],
+        )?;
+
+        // The mint authority PDA is also the rate authority, so update_rate goes through the program
+        let authority = ctx.accounts.mint_authority.key();
+        interest_bearing_mint_initialize(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InterestBearingMintInitialize {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                },
+            ),
+            Some(authority),
+            rate_bps,
+        )?;
+
+        initialize_mint2(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                },
+            ),
+            decimals,
+            &authority,
+            Some(&authority),
+        )?;
+
+        ctx.accounts.config.init(
+            ctx.accounts.admin.key(),
+            ctx.accounts.token_mint.key(),
+            decimals,
+            max_supply,
+            ctx.bumps.config,
+        );
+
+        msg!(
+            "Interest-bearing mint '{}' ({}) created at {} bps",
+            name,
+            ctx.accounts.token_mint.key(),
+            rate_bps
+        );
+
+        Ok(())
+    }
+
+    // Admin: change the interest rate of an interest-bearing mint
+    pub fn update_rate(ctx: Context<UpdateRate>, rate_bps: i16) -> Result<()> {
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+
+        interest_bearing_mint_update_rate(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                InterestBearingMintUpdateRate {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    rate_authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            rate_bps,
+        )?;
+
+        msg!(
+            "Interest rate for {} updated to {} bps",
+            ctx.accounts.token_mint.key(),
+            rate_bps
+        );
+
+        Ok(())
+    }
+
+    // Create a named Token-2022 mint that stores its own name/symbol/uri via the
+    // MetadataPointer extension, as an alternative to Metaplex metadata
+    pub fn create_mint_with_metadata(
+        ctx: Context<CreateMintWithMetadata>,
+        name: String,
+        decimals: u8,
+        max_supply: u64,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= MAX_MINT_NAME_LEN,
+            TokenDemoError::InvalidMintName
+        );
+        require!(decimals <= MAX_DECIMALS, TokenDemoError::InvalidDecimals);
+
+        // Metadata is appended to the mint account, so fund the extra rent up front:
+        // TLV header + update authority + mint + three length-prefixed strings
+        // + empty additional-metadata vec
+        let metadata_len = 4 + 32 + 32 + (4 + name.len()) + (4 + symbol.len()) + (4 + uri.len()) + 4;
+        let mint_info = ctx.accounts.token_mint.to_account_info();
+        let required = Rent::get()?.minimum_balance(mint_info.data_len() + metadata_len);
+        let top_up = required.saturating_sub(mint_info.lamports());
+        if top_up > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: mint_info,
+                    },
+                ),
+                top_up,
+            )?;
+        }
+
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TokenMetadataInitialize {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    metadata: ctx.accounts.token_mint.to_account_info(),
+                    mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                    update_authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            name.clone(),
+            symbol.clone(),
+            uri,
+        )?;
+
+        ctx.accounts.config.init(
+            ctx.accounts.admin.key(),
+            ctx.accounts.token_mint.key(),
+            decimals,
+            max_supply,
+            ctx.bumps.config,
+        );
+
+        msg!(
+            "Mint '{}' ({}) created with on-chain metadata, symbol={}",
+            name,
+            ctx.accounts.token_mint.key(),
+            symbol
+        );
+
+        Ok(())
+    }
+
+    // Admin: update one field of the mint's on-chain Token-2022 metadata
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        field: MetadataField,
+        value: String,
+    ) -> Result<()> {
+        // Token-2022 reallocs the mint to fit the new value; cover the worst case
+        let mint_info = ctx.accounts.token_mint.to_account_info();
+        let required = Rent::get()?.minimum_balance(mint_info.data_len() + value.len());
+        let top_up = required.saturating_sub(mint_info.lamports());
+        if top_up > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: mint_info,
+                    },
+                ),
+                top_up,
+            )?;
+        }
+
+        let spl_field = match field {
+            MetadataField::Name => Field::Name,
+            MetadataField::Symbol => Field::Symbol,
+            MetadataField::Uri => Field::Uri,
         };
-        
-        // Generate signer seeds for the mint authority PDA
-        let seeds = &[b"mint_authority", &[ctx.bumps.mint_authority]];
+
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
         let signer_seeds = &[&seeds[..]];
-        
-        // Create CPI context with signer seeds for the PDA
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(
-            cpi_program, 
-            cpi_accounts, 
-            signer_seeds
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TokenMetadataUpdateField {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    metadata: ctx.accounts.token_mint.to_account_info(),
+                    update_authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            spl_field,
+            value.clone(),
+        )?;
+
+        msg!("Token metadata {:?} updated to {}", field, value);
+
+        Ok(())
+    }
+
+    // Admin: attach Metaplex metadata (name, symbol, logo URI) to the mint
+    pub fn initialize_metadata(
+        ctx: Context<InitializeMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        // The mint authority PDA signs as both mint authority and update authority
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+
+        let data = DataV2 {
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        };
+        // Built with the Metaplex CPI builder rather than anchor-spl's wrapper, which
+        // requires the rent sysvar the Token Metadata program no longer reads
+        let token_metadata_program = ctx.accounts.token_metadata_program.to_account_info();
+        let mint_authority = ctx.accounts.mint_authority.to_account_info();
+        CreateMetadataAccountV3CpiBuilder::new(&token_metadata_program)
+            .metadata(&ctx.accounts.metadata.to_account_info())
+            .mint(&ctx.accounts.token_mint.to_account_info())
+            .mint_authority(&mint_authority)
+            .payer(&ctx.accounts.admin.to_account_info())
+            .update_authority(&mint_authority, true)
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .data(data)
+            .is_mutable(true)
+            .invoke_signed(signer_seeds)?;
+
+        msg!("Metadata created: name={}, symbol={}", name, symbol);
+
+        Ok(())
+    }
+
+    // Create a named Token-2022 mint carrying the TransferFeeConfig extension, with
+    // its config; the caller becomes its admin
+    pub fn configure_transfer_fee(
+        ctx: Context<ConfigureTransferFee>,
+        name: String,
+        decimals: u8,
+        max_supply: u64,
+        bps: u16,
+        max_fee: u64,
+    ) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= MAX_MINT_NAME_LEN,
+            TokenDemoError::InvalidMintName
         );
-        
-        // Execute mint instruction
-        token::mint_to(cpi_ctx, amount)?;
-        
-        // Log the event
+        require!(decimals <= MAX_DECIMALS, TokenDemoError::InvalidDecimals);
+        require!(bps <= 10_000, TokenDemoError::InvalidFee);
+
+        let mint_seeds = &[b"mint".as_ref(), name.as_bytes(), &[ctx.bumps.token_mint]];
+        create_extension_mint_account(
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[ExtensionType::TransferFeeConfig],
+            &mint_seeds[..],
+        )?;
+
+        // The mint authority PDA controls the fee config and withheld fees
+        let authority = ctx.accounts.mint_authority.key();
+        transfer_fee_initialize(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferFeeInitialize {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                },
+            ),
+            Some(&authority),
+            Some(&authority),
+            bps,
+            max_fee,
+        )?;
+
+        initialize_mint2(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                },
+            ),
+            decimals,
+            &authority,
+            None,
+        )?;
+
+        ctx.accounts.config.init(
+            ctx.accounts.admin.key(),
+            ctx.accounts.token_mint.key(),
+            decimals,
+            max_supply,
+            ctx.bumps.config,
+        );
+
         msg!(
-            "TOKEN_MINT_EVENT: recipient={}, amount={}", 
-            ctx.accounts.user.key(),
+            "Transfer-fee mint '{}' ({}) created: bps={}, max_fee={}",
+            name,
+            ctx.accounts.token_mint.key(),
+            bps,
+            max_fee
+        );
+
+        Ok(())
+    }
+
+    // Sweep withheld transfer fees from the given token accounts into the mint's
+    // fee vault; anyone can crank it
+    pub fn harvest_fees<'info>(ctx: Context<'_, '_, '_, 'info, HarvestFees<'info>>) -> Result<()> {
+        // Fees withheld in holder accounts are first moved to the mint (permissionless)...
+        let sources = ctx.remaining_accounts.to_vec();
+        if !sources.is_empty() {
+            harvest_withheld_tokens_to_mint(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    HarvestWithheldTokensToMint {
+                        token_program_id: ctx.accounts.token_program.to_account_info(),
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                    },
+                ),
+                sources,
+            )?;
+        }
+
+        // ...then withdrawn from the mint to the vault, signed by the withdraw authority PDA
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        withdraw_withheld_tokens_from_mint(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            WithdrawWithheldTokensFromMint {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                destination: ctx.accounts.fee_vault.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        msg!("Transfer fees harvested into {}", ctx.accounts.fee_vault.key());
+
+        Ok(())
+    }
+
+    // Admin: move harvested transfer fees out of the fee vault
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        require!(amount > 0, TokenDemoError::InvalidAmount);
+        require!(
+            ctx.accounts.fee_vault.amount >= amount,
+            TokenDemoError::InsufficientBalance
+        );
+
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.fee_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+
+        msg!(
+            "FEES_WITHDRAWN_EVENT: mint={}, to={}, amount={}",
+            ctx.accounts.token_mint.key(),
+            ctx.accounts.destination.key(),
             amount
         );
-        
+
+        Ok(())
+    }
+
+    // Admin: freeze a holder's token account using the PDA freeze authority
+    pub fn freeze_account(ctx: Context<FreezeOrThaw>) -> Result<()> {
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::freeze_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            FreezeAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        msg!("Token account {} frozen", ctx.accounts.token_account.key());
+
+        Ok(())
+    }
+
+    // Admin: thaw a previously frozen token account
+    pub fn thaw_account(ctx: Context<FreezeOrThaw>) -> Result<()> {
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            ThawAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        msg!("Token account {} thawed", ctx.accounts.token_account.key());
+
+        Ok(())
+    }
+
+    // Admin: permanently remove the mint authority so supply can never grow
+    pub fn finalize_supply(ctx: Context<ChangeMintAuthority>) -> Result<()> {
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.mint_authority.to_account_info(),
+                    account_or_mint: ctx.accounts.token_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        msg!(
+            "Supply finalized at {} for mint {}",
+            ctx.accounts.token_mint.supply,
+            ctx.accounts.token_mint.key()
+        );
+
+        Ok(())
+    }
+
+    // Admin: hand the mint authority from the PDA to another key (e.g. a multisig)
+    pub fn transfer_mint_authority(
+        ctx: Context<ChangeMintAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.mint_authority.to_account_info(),
+                    account_or_mint: ctx.accounts.token_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            AuthorityType::MintTokens,
+            Some(new_authority),
+        )?;
+
+        msg!(
+            "Mint authority for {} transferred to {}",
+            ctx.accounts.token_mint.key(),
+            new_authority
+        );
+
+        Ok(())
+    }
+
+    // Admin: hand the mint authority from the PDA to an SPL Token multisig, after
+    // checking the account really is an initialized multisig of the mint's program
+    pub fn rotate_to_multisig(ctx: Context<RotateToMultisig>) -> Result<()> {
+        let multisig = Multisig::unpack(&ctx.accounts.multisig.try_borrow_data()?)
+            .map_err(|_| TokenDemoError::InvalidMultisig)?;
+
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.mint_authority.to_account_info(),
+                    account_or_mint: ctx.accounts.token_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            AuthorityType::MintTokens,
+            Some(ctx.accounts.multisig.key()),
+        )?;
+
+        msg!(
+            "Mint authority for {} rotated to {}-of-{} multisig {}",
+            ctx.accounts.token_mint.key(),
+            multisig.m,
+            multisig.n,
+            ctx.accounts.multisig.key()
+        );
+
+        Ok(())
+    }
+
+    // Mint once the mint authority is a multisig; its signers are passed as remaining
+    // accounts and the token program enforces the M-of-N threshold
+    pub fn mint_with_multisig<'info>(
+        ctx: Context<'_, '_, '_, 'info, MintWithMultisig<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, TokenDemoError::MintingPaused);
+        require!(amount > 0, TokenDemoError::InvalidAmount);
+        require!(
+            ctx.accounts.token_mint.mint_authority == Some(ctx.accounts.multisig.key()).into(),
+            TokenDemoError::InvalidMultisig
+        );
+
+        let new_supply = ctx.accounts.token_mint.supply
+            .checked_add(amount)
+            .ok_or(TokenDemoError::SupplyCapExceeded)?;
+        require!(
+            new_supply <= ctx.accounts.config.max_supply,
+            TokenDemoError::SupplyCapExceeded
+        );
+
+        let signers = ctx.remaining_accounts;
+        require!(
+            signers.iter().all(|signer| signer.is_signer),
+            TokenDemoError::InvalidMultisig
+        );
+        let signer_keys: Vec<&Pubkey> = signers.iter().map(|signer| signer.key).collect();
+
+        let instruction = mint_to_instruction(
+            &ctx.accounts.token_program.key(),
+            &ctx.accounts.token_mint.key(),
+            &ctx.accounts.destination.key(),
+            &ctx.accounts.multisig.key(),
+            &signer_keys,
+            amount,
+        )?;
+        let mut account_infos = vec![
+            ctx.accounts.token_mint.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
+            ctx.accounts.multisig.to_account_info(),
+        ];
+        account_infos.extend(signers.iter().cloned());
+        invoke(&instruction, &account_infos)?;
+
+        // No faucet record is kept for multisig mints, so the destination is not
+        // counted as a unique recipient
+        record_mint_stats(&mut ctx.accounts.mint_stats, amount, false)?;
+
+        emit!(TokenMinted {
+            recipient: ctx.accounts.destination.owner,
+            amount,
+            new_supply,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // Admin: grow a config created by an older program version to the current layout
+    // and bump its version. New fields are zero-initialized, which is "disabled" for
+    // every optional feature
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let config_info = ctx.accounts.config.to_account_info();
+
+        // admin is the first field in every layout, so it can be checked before the
+        // account is large enough to deserialize
+        {
+            let data = config_info.try_borrow_data()?;
+            let admin = data
+                .get(8..40)
+                .and_then(|bytes| Pubkey::try_from(bytes).ok())
+                .ok_or(TokenDemoError::InvalidConfigAccount)?;
+            require_keys_eq!(admin, ctx.accounts.admin.key(), TokenDemoError::Unauthorized);
+        }
+
+        let new_len = 8 + Config::INIT_SPACE;
+        if config_info.data_len() < new_len {
+            let rent_due = Rent::get()?
+                .minimum_balance(new_len)
+                .saturating_sub(config_info.lamports());
+            if rent_due > 0 {
+                transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.admin.to_account_info(),
+                            to: config_info.clone(),
+                        },
+                    ),
+                    rent_due,
+                )?;
+            }
+            config_info.realloc(new_len, true)?;
+        }
+
+        let mut config = Config::try_deserialize(&mut &config_info.try_borrow_data()?[..])?;
+        let from_version = config.version;
+        require!(from_version <= CONFIG_VERSION, TokenDemoError::InvalidConfigAccount);
+        // 0 -> 1 only adds the version field itself; later upgrades go here
+        config.version = CONFIG_VERSION;
+        config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+        msg!(
+            "Config for {} migrated from version {} to {}",
+            config.mint,
+            from_version,
+            CONFIG_VERSION
+        );
+
+        Ok(())
+    }
+
+    // Anyone: grow a MintRecord created before recent_mints and last_drip_ts were
+    // added, so its wallet can mint again. The new fields are zero-filled, which is
+    // "no recent mints" and "never dripped"; the payer covers the extra rent
+    pub fn migrate_mint_record(ctx: Context<MigrateMintRecord>) -> Result<()> {
+        let record_info = ctx.accounts.mint_record.to_account_info();
+        let new_len = 8 + MintRecord::INIT_SPACE;
+        require!(
+            record_info.data_len() < new_len,
+            TokenDemoError::InvalidMintRecordAccount
+        );
+
+        let rent_due = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(record_info.lamports());
+        if rent_due > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: record_info.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        record_info.realloc(new_len, true)?;
+
+        // Fails unless the old layout was a prefix of the current one
+        MintRecord::try_deserialize(&mut &record_info.try_borrow_data()?[..])?;
+
+        msg!(
+            "MintRecord for {} on {} migrated",
+            ctx.accounts.user.key(),
+            ctx.accounts.token_mint.key()
+        );
+
+        Ok(())
+    }
+
+    // Admin: update the per-wallet lifetime cap and per-transaction ceiling; how
+    // often a wallet may mint is set with set_rate_limit
+    pub fn set_faucet_limits(
+        ctx: Context<UpdateConfig>,
+        max_per_wallet: u64,
+        max_per_tx: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.max_per_wallet = max_per_wallet;
+        config.max_per_tx = max_per_tx;
+
+        msg!(
+            "Faucet limits updated: max_per_wallet={}, max_per_tx={}",
+            max_per_wallet,
+            max_per_tx
+        );
+
+        Ok(())
+    }
+
+    // Admin: allow at most `max_mints` mint_tokens calls per wallet in any rolling
+    // `window_seconds`; max_mints = 0 disables the limiter
+    pub fn set_rate_limit(
+        ctx: Context<UpdateConfig>,
+        window_seconds: i64,
+        max_mints: u8,
+    ) -> Result<()> {
+        require!(
+            window_seconds > 0 && (max_mints as usize) <= MAX_WINDOW_MINTS,
+            TokenDemoError::InvalidRateLimit
+        );
+        let config = &mut ctx.accounts.config;
+        config.window_seconds = window_seconds;
+        config.max_mints_per_window = max_mints;
+
+        msg!(
+            "Rate limit updated: {} mints per {}s",
+            max_mints,
+            window_seconds
+        );
+
+        Ok(())
+    }
+
+    // Admin: require minters to hold at least `min_sol_balance` lamports and, unless
+    // `gate_mint` is Pubkey::default(), a non-zero balance of the gate token
+    pub fn set_sybil_gate(
+        ctx: Context<UpdateConfig>,
+        min_sol_balance: u64,
+        gate_mint: Pubkey,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.min_sol_balance = min_sol_balance;
+        config.gate_mint = gate_mint;
+
+        msg!(
+            "Sybil gate updated: min_sol_balance={}, gate_mint={}",
+            min_sol_balance,
+            gate_mint
+        );
+
+        Ok(())
+    }
+
+    // Admin: stop all minting until unpaused
+    pub fn pause_minting(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.config.paused = true;
+        msg!("Minting paused");
         Ok(())
     }
+
+    // Admin: resume minting
+    pub fn unpause_minting(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.config.paused = false;
+        msg!("Minting unpaused");
+        Ok(())
+    }
+
+    // Simple function to mint tokens to the user's wallet
+    pub fn mint_tokens(ctx: Context<MintTokens>, amount: u64, memo: Option<String>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        check_faucet_access(
+            &ctx.accounts.config,
+            &ctx.accounts.user,
+            ctx.accounts.allowlist_entry.as_ref(),
+            ctx.accounts.gate_token_account.as_ref(),
+            now,
+        )?;
+
+        require!(amount > 0, TokenDemoError::InvalidAmount);
+        if let Some(memo) = memo.as_ref() {
+            require!(memo.len() <= MAX_MEMO_LEN, TokenDemoError::MemoTooLong);
+        }
+        require!(
+            amount <= ctx.accounts.config.max_per_tx,
+            TokenDemoError::ExceedsPerTxLimit
+        );
+        require!(
+            ctx.accounts.token_mint.mint_authority.is_some(),
+            TokenDemoError::SupplyFinalized
+        );
+
+        // Optional referral bonus, minted to the referrer on top of the user's amount
+        let referral_bonus = match ctx.accounts.referrer.as_ref() {
+            Some(referrer) => {
+                require_keys_neq!(
+                    referrer.key(),
+                    ctx.accounts.user.key(),
+                    TokenDemoError::InvalidReferrer
+                );
+                (amount as u128)
+                    .checked_mul(ctx.accounts.config.referral_bonus_bps as u128)
+                    .map(|value| value / 10_000)
+                    .and_then(|value| u64::try_from(value).ok())
+                    .ok_or(TokenDemoError::MathOverflow)?
+            }
+            None => 0,
+        };
+
+        // Enforce the hard supply cap
+        let new_supply = ctx.accounts.token_mint.supply
+            .checked_add(amount)
+            .and_then(|value| value.checked_add(referral_bonus))
+            .ok_or(TokenDemoError::SupplyCapExceeded)?;
+        require!(
+            new_supply <= ctx.accounts.config.max_supply,
+            TokenDemoError::SupplyCapExceeded
+        );
+
+        // Enforce the per-wallet lifetime cap
+        let record = &mut ctx.accounts.mint_record;
+        let is_new_recipient = charge_wallet_cap(&ctx.accounts.config, record, amount)?;
+
+        // Enforce the sliding-window limiter over the wallet's most recent mints
+        let max_mints = ctx.accounts.config.max_mints_per_window as usize;
+        if max_mints > 0 {
+            let window = ctx.accounts.config.window_seconds;
+            let in_window = record.recent_mints
+                .iter()
+                .filter(|&&ts| ts != 0 && now.saturating_sub(ts) < window)
+                .count();
+            require!(in_window < max_mints, TokenDemoError::RateLimited);
+        }
+        // Overwrite the oldest slot; unused slots are 0 and so are always the oldest
+        if let Some(oldest) = record.recent_mints.iter_mut().min_by_key(|ts| **ts) {
+            *oldest = now;
+        }
+
+        record.last_mint_ts = now;
+
+        // The referral bonus counts against the referrer's own lifetime cap, so a
+        // second wallet cannot collect more through referrals than by minting itself
+        if referral_bonus > 0 {
+            let Some(referrer_record) = ctx.accounts.referrer_record.as_mut() else {
+                return err!(TokenDemoError::InvalidReferrer);
+            };
+            referrer_record.total_minted = referrer_record.total_minted
+                .checked_add(referral_bonus)
+                .ok_or(TokenDemoError::WalletCapExceeded)?;
+            require!(
+                referrer_record.total_minted <= ctx.accounts.config.max_per_wallet,
+                TokenDemoError::WalletCapExceeded
+            );
+        }
+
+        // Write an audit receipt, keyed by the per-mint nonce before it is bumped below
+        let receipt = &mut ctx.accounts.mint_receipt;
+        receipt.recipient = ctx.accounts.user.key();
+        receipt.mint = ctx.accounts.token_mint.key();
+        receipt.nonce = ctx.accounts.mint_stats.total_mints;
+        receipt.amount = amount;
+        receipt.timestamp = now;
+        receipt.slot = Clock::get()?.slot;
+
+        // Update mint statistics, including any referral bonus
+        record_mint_stats(
+            &mut ctx.accounts.mint_stats,
+            amount
+                .checked_add(referral_bonus)
+                .ok_or(TokenDemoError::MathOverflow)?,
+            is_new_recipient,
+        )?;
+
+        create_ata_idempotent(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        // Mint tokens to user
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.mint_authority.to_account_info(),
+        };
+        
+        // Generate signer seeds for the mint authority PDA
+        let seeds = &[b"mint_authority", &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        
+        // Create CPI context with signer seeds for the PDA
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(
+            cpi_program, 
+            cpi_accounts, 
+            signer_seeds
+        );
+        
+        // Execute mint instruction
+        token_interface::mint_to(cpi_ctx, amount)?;
+
+        if referral_bonus > 0 {
+            let (Some(referrer), Some(referrer_token_account), Some(referral_stats)) = (
+                ctx.accounts.referrer.as_ref(),
+                ctx.accounts.referrer_token_account.as_ref(),
+                ctx.accounts.referral_stats.as_mut(),
+            ) else {
+                return err!(TokenDemoError::InvalidReferrer);
+            };
+
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        to: referrer_token_account.to_account_info(),
+                        authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                referral_bonus,
+            )?;
+
+            referral_stats.referrer = referrer.key();
+            referral_stats.referrals = referral_stats.referrals
+                .checked_add(1)
+                .ok_or(TokenDemoError::MathOverflow)?;
+            referral_stats.total_bonus = referral_stats.total_bonus
+                .checked_add(referral_bonus)
+                .ok_or(TokenDemoError::MathOverflow)?;
+
+            msg!(
+                "Referral bonus of {} minted to {}",
+                referral_bonus,
+                referrer.key()
+            );
+        }
+        
+        // Attach the optional memo so explorers show it next to the mint
+        if let Some(memo) = memo {
+            let Some(memo_program) = ctx.accounts.memo_program.as_ref() else {
+                return err!(TokenDemoError::MissingMemoProgram);
+            };
+            build_memo(
+                CpiContext::new(memo_program.to_account_info(), BuildMemo {}),
+                memo.as_bytes(),
+            )?;
+        }
+
+        // Emit a structured event for the frontend
+        emit!(TokenMinted {
+            recipient: ctx.accounts.user.key(),
+            amount,
+            new_supply,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    // Admin: set the fixed amount claim_drip mints; 0 disables the drip
+    pub fn set_drip_amount(ctx: Context<UpdateConfig>, drip_amount: u64) -> Result<()> {
+        ctx.accounts.config.drip_amount = drip_amount;
+        msg!("Drip amount set to {}", drip_amount);
+        Ok(())
+    }
+
+    // Mint the configured drip amount, at most once per DRIP_INTERVAL_SECONDS per wallet
+    // The drip is a faucet mint like mint_tokens, so the same access rules, caps and
+    // stats apply; only the rate limit is its own
+    pub fn claim_drip(ctx: Context<ClaimDrip>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.config;
+        check_faucet_access(
+            config,
+            &ctx.accounts.user,
+            ctx.accounts.allowlist_entry.as_ref(),
+            ctx.accounts.gate_token_account.as_ref(),
+            now,
+        )?;
+        require!(config.drip_amount > 0, TokenDemoError::DripNotConfigured);
+        require!(
+            ctx.accounts.token_mint.mint_authority.is_some(),
+            TokenDemoError::SupplyFinalized
+        );
+
+        let record = &mut ctx.accounts.mint_record;
+        // A record that has never dripped has last_drip_ts == 0
+        require!(
+            record.last_drip_ts == 0
+                || now >= record.last_drip_ts.saturating_add(DRIP_INTERVAL_SECONDS),
+            TokenDemoError::DripNotReady
+        );
+
+        let amount = config.drip_amount;
+        let new_supply = ctx.accounts.token_mint.supply
+            .checked_add(amount)
+            .ok_or(TokenDemoError::SupplyCapExceeded)?;
+        require!(new_supply <= config.max_supply, TokenDemoError::SupplyCapExceeded);
+
+        let is_new_recipient = charge_wallet_cap(config, record, amount)?;
+        record.last_drip_ts = now;
+        record_mint_stats(&mut ctx.accounts.mint_stats, amount, is_new_recipient)?;
+
+        create_ata_idempotent(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(TokenMinted {
+            recipient: ctx.accounts.user.key(),
+            amount,
+            new_supply,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // Admin: mint to many recipients at once; recipient ATAs are passed as remaining accounts
+    pub fn batch_mint<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchMint<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        require!(
+            amounts.len() == ctx.remaining_accounts.len(),
+            TokenDemoError::BatchLengthMismatch
+        );
+        require!(
+            ctx.accounts.token_mint.mint_authority.is_some(),
+            TokenDemoError::SupplyFinalized
+        );
+
+        // Check the whole batch against the supply cap up front
+        let mut new_supply = ctx.accounts.token_mint.supply;
+        for amount in amounts.iter() {
+            new_supply = new_supply
+                .checked_add(*amount)
+                .ok_or(TokenDemoError::SupplyCapExceeded)?;
+        }
+        require!(
+            new_supply <= ctx.accounts.config.max_supply,
+            TokenDemoError::SupplyCapExceeded
+        );
+
+        let mint_key = ctx.accounts.token_mint.key();
+        let token_program_key = ctx.accounts.token_program.key();
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        let now = Clock::get()?.unix_timestamp;
+        let mut supply = ctx.accounts.token_mint.supply;
+
+        for (account_info, amount) in ctx.remaining_accounts.iter().zip(amounts.iter()) {
+            // Each account must be the canonical ATA of its owner for this mint
+            let recipient_account = InterfaceAccount::<TokenAccount>::try_from(account_info)?;
+            require_keys_eq!(
+                recipient_account.mint,
+                mint_key,
+                TokenDemoError::InvalidRecipientAccount
+            );
+            require_keys_eq!(
+                account_info.key(),
+                get_associated_token_address_with_program_id(
+                    &recipient_account.owner,
+                    &mint_key,
+                    &token_program_key
+                ),
+                TokenDemoError::InvalidRecipientAccount
+            );
+
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        to: account_info.clone(),
+                        authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                *amount,
+            )?;
+
+            supply += *amount;
+            emit!(TokenMinted {
+                recipient: recipient_account.owner,
+                amount: *amount,
+                new_supply: supply,
+                timestamp: now,
+            });
+        }
+
+        msg!("Batch minted to {} recipients", amounts.len());
+
+        Ok(())
+    }
+
+    // Admin: schedule when mint_tokens is live; 0 leaves that side of the window open
+    pub fn set_mint_window(
+        ctx: Context<UpdateConfig>,
+        mint_start_ts: i64,
+        mint_end_ts: i64,
+    ) -> Result<()> {
+        require!(
+            mint_start_ts == 0 || mint_end_ts == 0 || mint_start_ts < mint_end_ts,
+            TokenDemoError::InvalidMintWindow
+        );
+
+        let config = &mut ctx.accounts.config;
+        config.mint_start_ts = mint_start_ts;
+        config.mint_end_ts = mint_end_ts;
+
+        msg!("Mint window set: start={}, end={}", mint_start_ts, mint_end_ts);
+
+        Ok(())
+    }
+
+    // Admin: set the referral bonus, as basis points of the minted amount
+    pub fn set_referral_bonus(ctx: Context<UpdateConfig>, bonus_bps: u16) -> Result<()> {
+        require!(bonus_bps <= 10_000, TokenDemoError::InvalidReferralBonus);
+        ctx.accounts.config.referral_bonus_bps = bonus_bps;
+        msg!("Referral bonus set to {} bps", bonus_bps);
+        Ok(())
+    }
+
+    // Admin: require callers of mint_tokens to be on the allowlist
+    pub fn set_allowlist_only(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+        ctx.accounts.config.allowlist_only = enabled;
+        msg!("Allowlist-only minting set to {}", enabled);
+        Ok(())
+    }
+
+    // Admin: allow a wallet to mint while allowlist_only is enabled
+    pub fn add_to_allowlist(ctx: Context<AddToAllowlist>, wallet: Pubkey) -> Result<()> {
+        ctx.accounts.allowlist_entry.wallet = wallet;
+        msg!("Wallet {} added to the allowlist", wallet);
+        Ok(())
+    }
+
+    // Admin: remove a wallet from the allowlist, refunding the entry's rent
+    pub fn remove_from_allowlist(ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+        msg!(
+            "Wallet {} removed from the allowlist",
+            ctx.accounts.allowlist_entry.wallet
+        );
+        Ok(())
+    }
+
+    // Admin: publish the Merkle root of (wallet, amount) airdrop allocations
+    pub fn set_airdrop_root(ctx: Context<UpdateConfig>, root: [u8; 32]) -> Result<()> {
+        ctx.accounts.config.airdrop_root = root;
+        msg!("Airdrop root updated");
+        Ok(())
+    }
+
+    // Claim an airdrop allocation by proving it is part of the published Merkle tree
+    pub fn claim_airdrop(
+        ctx: Context<ClaimAirdrop>,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(
+            ctx.accounts.token_mint.mint_authority.is_some(),
+            TokenDemoError::SupplyFinalized
+        );
+
+        let leaf = hashv(&[ctx.accounts.claimer.key().as_ref(), &amount.to_le_bytes()]).to_bytes();
+        require!(
+            verify_merkle_proof(&proof, config.airdrop_root, leaf),
+            TokenDemoError::InvalidMerkleProof
+        );
+
+        let new_supply = ctx.accounts.token_mint.supply
+            .checked_add(amount)
+            .ok_or(TokenDemoError::SupplyCapExceeded)?;
+        require!(new_supply <= config.max_supply, TokenDemoError::SupplyCapExceeded);
+
+        // The claim marker is created with `init`, so a second claim fails
+        let now = Clock::get()?.unix_timestamp;
+        let claim = &mut ctx.accounts.claim_marker;
+        claim.claimer = ctx.accounts.claimer.key();
+        claim.amount = amount;
+        claim.claimed_at = now;
+
+        create_ata_idempotent(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.claimer.to_account_info(),
+            &ctx.accounts.claimer_token_account.to_account_info(),
+            &ctx.accounts.claimer.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.claimer_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(TokenMinted {
+            recipient: ctx.accounts.claimer.key(),
+            amount,
+            new_supply,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // Admin: set the backend key whose ed25519 vouchers mint_with_voucher accepts;
+    // Pubkey::default() disables voucher minting
+    pub fn set_voucher_signer(ctx: Context<UpdateConfig>, signer: Pubkey) -> Result<()> {
+        ctx.accounts.config.voucher_signer = signer;
+        msg!("Voucher signer set to {}", signer);
+        Ok(())
+    }
+
+    // Mint with a voucher signed off-chain by config.voucher_signer. The transaction must
+    // carry an ed25519 program instruction, immediately before this one, that verifies
+    // `signature` over (mint, recipient, amount, expiry)
+    pub fn mint_with_voucher(
+        ctx: Context<MintWithVoucher>,
+        amount: u64,
+        expiry: i64,
+        signature: [u8; 64],
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(!config.paused, TokenDemoError::MintingPaused);
+        require!(amount > 0, TokenDemoError::InvalidAmount);
+        require!(
+            config.voucher_signer != Pubkey::default(),
+            TokenDemoError::InvalidVoucher
+        );
+        require!(
+            ctx.accounts.token_mint.mint_authority.is_some(),
+            TokenDemoError::SupplyFinalized
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now <= expiry, TokenDemoError::VoucherExpired);
+
+        let message = [
+            ctx.accounts.token_mint.key().as_ref(),
+            ctx.accounts.recipient.key().as_ref(),
+            &amount.to_le_bytes(),
+            &expiry.to_le_bytes(),
+        ]
+        .concat();
+        verify_ed25519_voucher(
+            &ctx.accounts.instructions,
+            &config.voucher_signer,
+            &signature,
+            &message,
+        )?;
+
+        let new_supply = ctx.accounts.token_mint.supply
+            .checked_add(amount)
+            .ok_or(TokenDemoError::SupplyCapExceeded)?;
+        require!(new_supply <= config.max_supply, TokenDemoError::SupplyCapExceeded);
+
+        // The redemption marker is created with `init`, so a voucher works only once
+        let redemption = &mut ctx.accounts.voucher_redemption;
+        redemption.recipient = ctx.accounts.recipient.key();
+        redemption.amount = amount;
+        redemption.redeemed_at = now;
+
+        create_ata_idempotent(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.recipient.to_account_info(),
+            &ctx.accounts.recipient_token_account.to_account_info(),
+            &ctx.accounts.recipient.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(TokenMinted {
+            recipient: ctx.accounts.recipient.key(),
+            amount,
+            new_supply,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // Admin: configure the SOL price used by buy_tokens
+    pub fn set_sale_params(
+        ctx: Context<UpdateConfig>,
+        tokens_per_sol: u64,
+        min_purchase_lamports: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.tokens_per_sol = tokens_per_sol;
+        config.min_purchase_lamports = min_purchase_lamports;
+
+        msg!(
+            "Sale params updated: tokens_per_sol={}, min_purchase_lamports={}",
+            tokens_per_sol,
+            min_purchase_lamports
+        );
+
+        Ok(())
+    }
+
+    // Buy tokens with SOL: lamports go to the treasury PDA, tokens are minted at the config rate
+    pub fn buy_tokens(ctx: Context<BuyTokens>, lamports: u64) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(!config.paused, TokenDemoError::MintingPaused);
+        require!(
+            ctx.accounts.token_mint.mint_authority.is_some(),
+            TokenDemoError::SupplyFinalized
+        );
+
+        let token_amount = config.quote_lamports(lamports, ctx.accounts.token_mint.supply)?;
+        let new_supply = ctx.accounts.token_mint.supply
+            .checked_add(token_amount)
+            .ok_or(TokenDemoError::SupplyCapExceeded)?;
+
+        // Pay the treasury
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+
+        create_ata_idempotent(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            token_amount,
+        )?;
+
+        // Only tokens bought here are backed by treasury SOL, so only they can be
+        // redeemed, and only they share the treasury
+        let sale_stats = &mut ctx.accounts.sale_stats;
+        sale_stats.tokens_sold = sale_stats.tokens_sold
+            .checked_add(token_amount)
+            .ok_or(TokenDemoError::MathOverflow)?;
+        let purchase = &mut ctx.accounts.purchase_record;
+        purchase.buyer = ctx.accounts.user.key();
+        purchase.purchased = purchase.purchased
+            .checked_add(token_amount)
+            .ok_or(TokenDemoError::MathOverflow)?;
+
+        emit!(TokenMinted {
+            recipient: ctx.accounts.user.key(),
+            amount: token_amount,
+            new_supply,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // Read-only: how many base units buy_tokens would mint for `lamports`. Anchor
+    // sends the value back with set_return_data, so frontends read it by simulating
+    pub fn quote(ctx: Context<Quote>, lamports: u64) -> Result<u64> {
+        ctx.accounts.config.quote_lamports(lamports, ctx.accounts.token_mint.supply)
+    }
+
+    // Read-only: supply, mint counters and paused state in one call, returned the
+    // same way as quote so a single simulation replaces several account fetches
+    pub fn get_stats(ctx: Context<GetStats>) -> Result<StatsView> {
+        let stats = &ctx.accounts.mint_stats;
+        Ok(StatsView {
+            supply: ctx.accounts.token_mint.supply,
+            max_supply: ctx.accounts.config.max_supply,
+            total_minted: stats.total_minted,
+            total_mints: stats.total_mints,
+            unique_recipients: stats.unique_recipients,
+            paused: ctx.accounts.config.paused,
+        })
+    }
+
+    // Admin: accept an SPL token (e.g. devnet USDC) as payment at the given rate
+    pub fn set_payment_mint(
+        ctx: Context<SetPaymentMint>,
+        tokens_per_payment_token: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.payment_mint = ctx.accounts.payment_mint.key();
+        config.tokens_per_payment_token = tokens_per_payment_token;
+
+        msg!(
+            "Payment mint set to {}, tokens_per_payment_token={}",
+            config.payment_mint,
+            tokens_per_payment_token
+        );
+
+        Ok(())
+    }
+
+    // Buy tokens by paying with the configured SPL payment mint
+    pub fn buy_tokens_with_spl(ctx: Context<BuyTokensWithSpl>, amount: u64) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(!config.paused, TokenDemoError::MintingPaused);
+        require!(config.tokens_per_payment_token > 0, TokenDemoError::SaleNotConfigured);
+        require!(amount > 0, TokenDemoError::InvalidAmount);
+        require!(
+            ctx.accounts.token_mint.mint_authority.is_some(),
+            TokenDemoError::SupplyFinalized
+        );
+
+        // base units = amount * rate * 10^decimals / 10^payment_decimals, since the rate
+        // is whole tokens per whole payment token
+        let payment_decimals = ctx.accounts.payment_mint.decimals;
+        let token_amount = (amount as u128)
+            .checked_mul(config.tokens_per_payment_token as u128)
+            .and_then(|value| value.checked_mul(10u128.pow(config.decimals as u32)))
+            .and_then(|value| value.checked_div(10u128.pow(payment_decimals as u32)))
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(TokenDemoError::MathOverflow)?;
+        require!(token_amount > 0, TokenDemoError::BelowMinimumPurchase);
+
+        let new_supply = ctx.accounts.token_mint.supply
+            .checked_add(token_amount)
+            .ok_or(TokenDemoError::SupplyCapExceeded)?;
+        require!(new_supply <= config.max_supply, TokenDemoError::SupplyCapExceeded);
+
+        // Move the payment into the program-owned vault
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.payment_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_payment_account.to_account_info(),
+                    mint: ctx.accounts.payment_mint.to_account_info(),
+                    to: ctx.accounts.payment_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+            payment_decimals,
+        )?;
+
+        create_ata_idempotent(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            token_amount,
+        )?;
+
+        emit!(TokenMinted {
+            recipient: ctx.accounts.user.key(),
+            amount: token_amount,
+            new_supply,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // Burn tokens bought with buy_tokens and receive their pro-rata share of the SOL
+    // held by the treasury PDA. Tokens minted for free are not backed and cannot redeem
+    pub fn redeem(ctx: Context<Redeem>, token_amount: u64) -> Result<()> {
+        require!(!ctx.accounts.config.paused, TokenDemoError::MintingPaused);
+        require!(token_amount > 0, TokenDemoError::InvalidAmount);
+        require!(
+            ctx.accounts.user_token_account.amount >= token_amount,
+            TokenDemoError::InsufficientBalance
+        );
+        require!(
+            ctx.accounts.purchase_record.purchased >= token_amount,
+            TokenDemoError::NotPurchased
+        );
+
+        // Keep the treasury rent-exempt; only the excess backs the sold tokens
+        let treasury_info = ctx.accounts.treasury.to_account_info();
+        let reserve = Rent::get()?.minimum_balance(0);
+        let backing = treasury_info.lamports().saturating_sub(reserve);
+        let tokens_sold = ctx.accounts.sale_stats.tokens_sold;
+
+        // lamports = token_amount * backing / tokens_sold, measured before the burn
+        let lamports = (token_amount as u128)
+            .checked_mul(backing as u128)
+            .and_then(|value| value.checked_div(tokens_sold as u128))
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(TokenDemoError::MathOverflow)?;
+        require!(lamports > 0, TokenDemoError::NothingToRedeem);
+
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            token_amount,
+        )?;
+
+        // The treasury is a system-owned PDA, so the program signs the transfer for it
+        let mint_key = ctx.accounts.token_mint.key();
+        let treasury_seeds = &[b"treasury".as_ref(), mint_key.as_ref(), &[ctx.bumps.treasury]];
+        invoke_signed(
+            &system_instruction::transfer(&treasury_info.key(), &ctx.accounts.user.key(), lamports),
+            &[
+                treasury_info,
+                ctx.accounts.user.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&treasury_seeds[..]],
+        )?;
+
+        let sale_stats = &mut ctx.accounts.sale_stats;
+        sale_stats.tokens_sold = sale_stats.tokens_sold
+            .checked_sub(token_amount)
+            .ok_or(TokenDemoError::MathOverflow)?;
+        let purchase = &mut ctx.accounts.purchase_record;
+        purchase.purchased = purchase.purchased
+            .checked_sub(token_amount)
+            .ok_or(TokenDemoError::MathOverflow)?;
+
+        msg!(
+            "TOKEN_REDEEM_EVENT: owner={}, tokens={}, lamports={}",
+            ctx.accounts.user.key(),
+            token_amount,
+            lamports
+        );
+
+        Ok(())
+    }
+
+    // Admin: mint tokens into a vault that unlocks linearly to the beneficiary over `duration` seconds
+    pub fn mint_vested(ctx: Context<MintVested>, amount: u64, duration: i64) -> Result<()> {
+        require!(amount > 0, TokenDemoError::InvalidAmount);
+        require!(duration > 0, TokenDemoError::InvalidVestingDuration);
+        require!(
+            ctx.accounts.token_mint.mint_authority.is_some(),
+            TokenDemoError::SupplyFinalized
+        );
+
+        let new_supply = ctx.accounts.token_mint.supply
+            .checked_add(amount)
+            .ok_or(TokenDemoError::SupplyCapExceeded)?;
+        require!(
+            new_supply <= ctx.accounts.config.max_supply,
+            TokenDemoError::SupplyCapExceeded
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        schedule.beneficiary = ctx.accounts.beneficiary.key();
+        schedule.mint = ctx.accounts.token_mint.key();
+        schedule.total_amount = amount;
+        schedule.claimed_amount = 0;
+        schedule.start_ts = now;
+        schedule.duration = duration;
+        schedule.bump = ctx.bumps.vesting_schedule;
+
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.vesting_vault.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(TokenMinted {
+            recipient: ctx.accounts.vesting_vault.key(),
+            amount,
+            new_supply,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // Release the linearly unlocked, not yet claimed part of a vesting schedule
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let schedule = &ctx.accounts.vesting_schedule;
+
+        // unlocked = total * min(elapsed, duration) / duration
+        let elapsed = now.saturating_sub(schedule.start_ts).clamp(0, schedule.duration);
+        let unlocked = (schedule.total_amount as u128)
+            .checked_mul(elapsed as u128)
+            .and_then(|value| value.checked_div(schedule.duration as u128))
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(TokenDemoError::MathOverflow)?;
+        let claimable = unlocked.saturating_sub(schedule.claimed_amount);
+        require!(claimable > 0, TokenDemoError::NothingToClaim);
+
+        create_ata_idempotent(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.beneficiary.to_account_info(),
+            &ctx.accounts.beneficiary_token_account.to_account_info(),
+            &ctx.accounts.beneficiary.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        // The vault is owned by the schedule PDA, which signs the release
+        let beneficiary_key = schedule.beneficiary;
+        let mint_key = schedule.mint;
+        let seeds = &[
+            b"vesting".as_ref(),
+            mint_key.as_ref(),
+            beneficiary_key.as_ref(),
+            &[schedule.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.vesting_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                    authority: ctx.accounts.vesting_schedule.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            claimable,
+            ctx.accounts.token_mint.decimals,
+        )?;
+
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        schedule.claimed_amount = schedule.claimed_amount
+            .checked_add(claimable)
+            .ok_or(TokenDemoError::MathOverflow)?;
+
+        msg!(
+            "Vested tokens claimed: beneficiary={}, amount={}, claimed={}/{}",
+            beneficiary_key,
+            claimable,
+            schedule.claimed_amount,
+            schedule.total_amount
+        );
+
+        Ok(())
+    }
+
+    // Admin: mint tokens into an escrow vault the beneficiary can release once `unlock_ts` passes
+    pub fn mint_to_escrow(ctx: Context<MintToEscrow>, amount: u64, unlock_ts: i64) -> Result<()> {
+        require!(amount > 0, TokenDemoError::InvalidAmount);
+        require!(
+            ctx.accounts.token_mint.mint_authority.is_some(),
+            TokenDemoError::SupplyFinalized
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(unlock_ts > now, TokenDemoError::InvalidUnlockTime);
+
+        let new_supply = ctx.accounts.token_mint.supply
+            .checked_add(amount)
+            .ok_or(TokenDemoError::SupplyCapExceeded)?;
+        require!(
+            new_supply <= ctx.accounts.config.max_supply,
+            TokenDemoError::SupplyCapExceeded
+        );
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.beneficiary = ctx.accounts.beneficiary.key();
+        escrow.mint = ctx.accounts.token_mint.key();
+        escrow.amount = amount;
+        escrow.unlock_ts = unlock_ts;
+        escrow.bump = ctx.bumps.escrow;
+
+        let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+        let signer_seeds = &[&seeds[..]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.escrow_vault.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(TokenMinted {
+            recipient: ctx.accounts.escrow_vault.key(),
+            amount,
+            new_supply,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // Release an unlocked escrow to the beneficiary, closing the vault and escrow
+    pub fn release_escrow(ctx: Context<ReleaseEscrow>) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= escrow.unlock_ts, TokenDemoError::EscrowLocked);
+
+        create_ata_idempotent(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.beneficiary.to_account_info(),
+            &ctx.accounts.beneficiary_token_account.to_account_info(),
+            &ctx.accounts.beneficiary.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        // The vault is owned by the escrow PDA, which signs the release
+        let seeds = &[
+            b"escrow".as_ref(),
+            escrow.mint.as_ref(),
+            escrow.beneficiary.as_ref(),
+            &[escrow.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let amount = ctx.accounts.escrow_vault.amount;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_vault.to_account_info(),
+                destination: ctx.accounts.beneficiary.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        msg!(
+            "Escrow released: beneficiary={}, amount={}",
+            ctx.accounts.beneficiary.key(),
+            amount
+        );
+
+        Ok(())
+    }
+
+    // Record the caller's current balance alongside supply and slot, for later
+    // pro-rata reward calculations; calling again overwrites the previous snapshot
+    pub fn snapshot_balance(ctx: Context<SnapshotBalance>) -> Result<()> {
+        let clock = Clock::get()?;
+        let snapshot = &mut ctx.accounts.balance_snapshot;
+        snapshot.owner = ctx.accounts.user.key();
+        snapshot.mint = ctx.accounts.token_mint.key();
+        snapshot.balance = ctx.accounts.user_token_account.amount;
+        snapshot.supply = ctx.accounts.token_mint.supply;
+        snapshot.slot = clock.slot;
+        snapshot.timestamp = clock.unix_timestamp;
+
+        msg!(
+            "BALANCE_SNAPSHOT_EVENT: owner={}, balance={}, supply={}, slot={}",
+            snapshot.owner,
+            snapshot.balance,
+            snapshot.supply,
+            snapshot.slot
+        );
+
+        Ok(())
+    }
+
+    // Close a mint receipt, returning its rent to the recipient who paid for it
+    pub fn close_mint_receipt(ctx: Context<CloseMintReceipt>) -> Result<()> {
+        msg!(
+            "Mint receipt {} closed for {}",
+            ctx.accounts.mint_receipt.nonce,
+            ctx.accounts.recipient.key()
+        );
+        Ok(())
+    }
+
+    // Burn tokens from the user's wallet
+    pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
+        require!(amount > 0, TokenDemoError::InvalidAmount);
+        require!(
+            ctx.accounts.user_token_account.amount >= amount,
+            TokenDemoError::InsufficientBalance
+        );
+
+        // The user owns the token account, so no PDA signer is needed
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.token_mint.to_account_info(),
+            from: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+        );
+
+        token_interface::burn(cpi_ctx, amount)?;
+
+        msg!(
+            "TOKEN_BURN_EVENT: owner={}, amount={}",
+            ctx.accounts.user.key(),
+            amount
+        );
+
+        Ok(())
+    }
+
+    // Close the user's token account, burning any leftover balance first, and refund its rent
+    pub fn close_user_token_account(ctx: Context<CloseUserTokenAccount>) -> Result<()> {
+        // Token accounts can only be closed when empty
+        let dust = ctx.accounts.user_token_account.amount;
+        if dust > 0 {
+            token_interface::burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        from: ctx.accounts.user_token_account.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                dust,
+            )?;
+        }
+
+        token_interface::close_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.user_token_account.to_account_info(),
+                destination: ctx.accounts.user.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ))?;
+
+        msg!(
+            "Token account closed for {}, burned {} dust",
+            ctx.accounts.user.key(),
+            dust
+        );
+
+        Ok(())
+    }
+
+    // Let another wallet spend up to `amount` tokens from the user's token account
+    pub fn approve_delegate(ctx: Context<ApproveDelegate>, amount: u64) -> Result<()> {
+        require!(amount > 0, TokenDemoError::InvalidAmount);
+
+        token_interface::approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Approve {
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    delegate: ctx.accounts.delegate.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!(
+            "TOKEN_APPROVE_EVENT: owner={}, delegate={}, amount={}",
+            ctx.accounts.user.key(),
+            ctx.accounts.delegate.key(),
+            amount
+        );
+
+        Ok(())
+    }
+
+    // Remove any delegate from the user's token account
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        token_interface::revoke(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Revoke {
+                source: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ))?;
+
+        msg!("TOKEN_REVOKE_EVENT: owner={}", ctx.accounts.user.key());
+
+        Ok(())
+    }
+
+    // Wrap SOL: move lamports into the user's wSOL ATA and sync its token balance
+    pub fn wrap_sol(ctx: Context<WrapSol>, lamports: u64) -> Result<()> {
+        require!(lamports > 0, TokenDemoError::InvalidAmount);
+
+        create_ata_idempotent(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.wsol_account.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.native_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: ctx.accounts.wsol_account.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+
+        // Without sync_native the token amount would not reflect the new lamports
+        token_interface::sync_native(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SyncNative {
+                account: ctx.accounts.wsol_account.to_account_info(),
+            },
+        ))?;
+
+        msg!("Wrapped {} lamports for {}", lamports, ctx.accounts.user.key());
+
+        Ok(())
+    }
+
+    // Unwrap SOL: close the user's wSOL ATA, returning every lamport (balance + rent)
+    pub fn unwrap_sol(ctx: Context<UnwrapSol>) -> Result<()> {
+        let amount = ctx.accounts.wsol_account.amount;
+
+        token_interface::close_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.wsol_account.to_account_info(),
+                destination: ctx.accounts.user.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ))?;
+
+        msg!("Unwrapped {} lamports for {}", amount, ctx.accounts.user.key());
+
+        Ok(())
+    }
+
+    // Transfer tokens from the user's wallet to another wallet
+    pub fn transfer_tokens(ctx: Context<TransferTokens>, amount: u64) -> Result<()> {
+        require!(amount > 0, TokenDemoError::InvalidAmount);
+        require!(
+            ctx.accounts.user_token_account.amount >= amount,
+            TokenDemoError::InsufficientBalance
+        );
+
+        create_ata_idempotent(
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.recipient_token_account.to_account_info(),
+            &ctx.accounts.recipient.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        // transfer_checked is required by Token-2022 and also works on the legacy program
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.recipient_token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+        );
+
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+
+        msg!(
+            "TOKEN_TRANSFER_EVENT: from={}, to={}, amount={}",
+            ctx.accounts.user.key(),
+            ctx.accounts.recipient.key(),
+            amount
+        );
+
+        Ok(())
+    }
+}
+
+// Create `owner`'s ATA for `mint` if it is missing; a no-op when it already exists.
+// Used instead of init_if_needed, which costs more compute and needs the rent sysvar
+fn create_ata_idempotent<'info>(
+    associated_token_program: &Program<'info, AssociatedToken>,
+    payer: &AccountInfo<'info>,
+    associated_token: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    associated_token::create_idempotent(CpiContext::new(
+        associated_token_program.to_account_info(),
+        Create {
+            payer: payer.clone(),
+            associated_token: associated_token.clone(),
+            authority: owner.clone(),
+            mint: mint.clone(),
+            system_program: system_program.to_account_info(),
+            token_program: token_program.clone(),
+        },
+    ))
+}
+
+// Access rules shared by the faucet paths (mint_tokens and claim_drip): pause,
+// allowlist, anti-sybil gate and the minting window
+fn check_faucet_access(
+    config: &Config,
+    user: &AccountInfo,
+    allowlist_entry: Option<&Account<AllowlistEntry>>,
+    gate_token_account: Option<&InterfaceAccount<TokenAccount>>,
+    now: i64,
+) -> Result<()> {
+    require!(!config.paused, TokenDemoError::MintingPaused);
+    require!(
+        !config.allowlist_only || allowlist_entry.is_some(),
+        TokenDemoError::NotAllowlisted
+    );
+
+    // Anti-sybil gating: a minimum SOL balance and/or holding the gate token
+    require!(
+        user.lamports() >= config.min_sol_balance,
+        TokenDemoError::SybilCheckFailed
+    );
+    if config.gate_mint != Pubkey::default() {
+        let holds_gate_token = gate_token_account
+            .is_some_and(|account| account.mint == config.gate_mint && account.amount > 0);
+        require!(holds_gate_token, TokenDemoError::SybilCheckFailed);
+    }
+
+    require!(
+        config.mint_start_ts == 0 || now >= config.mint_start_ts,
+        TokenDemoError::MintNotStarted
+    );
+    require!(
+        config.mint_end_ts == 0 || now < config.mint_end_ts,
+        TokenDemoError::MintEnded
+    );
+    Ok(())
+}
+
+// Charge `amount` against the wallet's lifetime cap. Returns whether the wallet
+// had never received faucet tokens, i.e. its record was just created
+fn charge_wallet_cap(config: &Config, record: &mut MintRecord, amount: u64) -> Result<bool> {
+    let is_new_recipient = record.total_minted == 0;
+    record.total_minted = record.total_minted
+        .checked_add(amount)
+        .ok_or(TokenDemoError::WalletCapExceeded)?;
+    require!(
+        record.total_minted <= config.max_per_wallet,
+        TokenDemoError::WalletCapExceeded
+    );
+    Ok(is_new_recipient)
+}
+
+// Count one mint of `amount` base units in the mint's statistics
+fn record_mint_stats(stats: &mut MintStats, amount: u64, is_new_recipient: bool) -> Result<()> {
+    stats.total_minted = stats.total_minted
+        .checked_add(amount)
+        .ok_or(TokenDemoError::MathOverflow)?;
+    stats.total_mints = stats.total_mints
+        .checked_add(1)
+        .ok_or(TokenDemoError::MathOverflow)?;
+    if is_new_recipient {
+        stats.unique_recipients = stats.unique_recipients
+            .checked_add(1)
+            .ok_or(TokenDemoError::MathOverflow)?;
+    }
+    Ok(())
+}
+
+// Extensions must be initialized before the mint itself, so Token-2022 mints with
+// extensions Anchor has no constraint for are created by hand: allocate the PDA with
+// room for `extensions`, then the caller initializes each extension and the mint
+fn create_extension_mint_account<'info>(
+    payer: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    extensions: &[ExtensionType],
+    mint_seeds: &[&[u8]],
+) -> Result<()> {
+    let space = ExtensionType::try_calculate_account_len::<MintState>(extensions)?;
+    let lamports = Rent::get()?.minimum_balance(space);
+    create_account(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            CreateAccount {
+                from: payer.clone(),
+                to: mint.clone(),
+            },
+            &[mint_seeds],
+        ),
+        lamports,
+        space as u64,
+        &Token2022::id(),
+    )
+}
+
+// Verify a Merkle proof using sorted-pair sha256 hashing
+fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed = leaf;
+    for node in proof.iter() {
+        computed = if computed <= *node {
+            hashv(&[&computed, node]).to_bytes()
+        } else {
+            hashv(&[node, &computed]).to_bytes()
+        };
+    }
+    computed == root
+}
+
+// Check that the instruction before the current one is an ed25519 program call
+// verifying exactly `signature` by `signer` over `message`, with all data inline
+fn verify_ed25519_voucher(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    signature: &[u8; 64],
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions)?;
+    require!(current_index > 0, TokenDemoError::InvalidVoucher);
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, TokenDemoError::InvalidVoucher);
+
+    // Layout: num_signatures (u8), padding (u8), then seven u16 offsets per signature
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, TokenDemoError::InvalidVoucher);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_offset = read_u16(2) as usize;
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    // u16::MAX means the data lives in the ed25519 instruction itself
+    require!(
+        read_u16(4) == u16::MAX && read_u16(8) == u16::MAX && read_u16(14) == u16::MAX,
+        TokenDemoError::InvalidVoucher
+    );
+
+    let slice = |offset: usize, len: usize| data.get(offset..offset + len);
+    require!(
+        slice(public_key_offset, 32) == Some(signer.as_ref())
+            && slice(signature_offset, 64) == Some(signature.as_ref())
+            && message_size == message.len()
+            && slice(message_offset, message_size) == Some(message),
+        TokenDemoError::InvalidVoucher
+    );
+
+    Ok(())
+}
+
+// PDA derivations for clients and for programs calling in via CPI. Depend on this
+// crate with its `cpi` feature to get typed `spl_token_demo::cpi::*` helpers:
+//   spl_token_demo = { path = "../spl", features = ["cpi"] }
+pub mod pda {
+    use super::*;
+
+    pub fn mint_authority() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"mint_authority"], &crate::ID)
+    }
+
+    pub fn mint(name: &str) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"mint", name.as_bytes()], &crate::ID)
+    }
+
+    pub fn config(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"config", mint.as_ref()], &crate::ID)
+    }
+
+    pub fn mint_stats(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"mint_stats", mint.as_ref()], &crate::ID)
+    }
+
+    pub fn mint_record(mint: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"mint_record", mint.as_ref(), user.as_ref()], &crate::ID)
+    }
+
+    // `nonce` is MintStats::total_mints before the mint_tokens call
+    pub fn mint_receipt(mint: &Pubkey, user: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"receipt", mint.as_ref(), user.as_ref(), &nonce.to_le_bytes()],
+            &crate::ID,
+        )
+    }
+
+    pub fn allowlist_entry(mint: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"allowlist", mint.as_ref(), wallet.as_ref()], &crate::ID)
+    }
+
+    pub fn treasury(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"treasury", mint.as_ref()], &crate::ID)
+    }
+
+    pub fn fee_vault(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"fee_vault", mint.as_ref()], &crate::ID)
+    }
+
+    pub fn sale_stats(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"sale_stats", mint.as_ref()], &crate::ID)
+    }
+
+    pub fn purchase_record(mint: &Pubkey, buyer: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"purchase", mint.as_ref(), buyer.as_ref()], &crate::ID)
+    }
+}
+
+// Token-2022 metadata fields that update_token_metadata can change
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub enum MetadataField {
+    Name,
+    Symbol,
+    Uri,
+}
+
+// Return value of get_stats
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct StatsView {
+    pub supply: u64,
+    pub max_supply: u64,
+    pub total_minted: u64,
+    pub total_mints: u64,
+    pub unique_recipients: u64,
+    pub paused: bool,
+}
+
+// Names become PDA seeds, which are limited to 32 bytes
+pub const MAX_MINT_NAME_LEN: usize = 32;
+// Same upper bound as SOL
+pub const MAX_DECIMALS: u8 = 9;
+// Minimum seconds between claim_drip calls from the same wallet
+pub const DRIP_INTERVAL_SECONDS: i64 = 86_400;
+// Longest memo mint_tokens will forward to the Memo program
+pub const MAX_MEMO_LEN: usize = 256;
+// Mint timestamps kept per wallet, and so the largest configurable window limit
+pub const MAX_WINDOW_MINTS: usize = 10;
+
+// Current Config layout version, written by create_mint and migrate_config
+pub const CONFIG_VERSION: u8 = 1;
+
+// Per-mint settings, created by create_mint
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    // Decimals chosen at create_mint; used by pricing math
+    pub decimals: u8,
+    // Hard cap on total supply, in base units
+    pub max_supply: u64,
+    // Formerly the per-wallet cooldown, replaced by the sliding-window limiter;
+    // unused, and kept only so the fields after it stay in place
+    pub reserved: i64,
+    // Lifetime amount a single wallet may mint
+    pub max_per_wallet: u64,
+    // Largest amount a single mint_tokens call may request
+    pub max_per_tx: u64,
+    // When true, mint_tokens is rejected
+    pub paused: bool,
+    // When true, mint_tokens requires the caller's allowlist entry
+    pub allowlist_only: bool,
+    // Extra tokens minted to a referrer, in basis points of the minted amount
+    pub referral_bonus_bps: u16,
+    // Unix timestamps bounding when mint_tokens is live; 0 means unbounded
+    pub mint_start_ts: i64,
+    pub mint_end_ts: i64,
+    // Merkle root of (wallet, amount) airdrop allocations
+    pub airdrop_root: [u8; 32],
+    // Whole tokens sold per 1 SOL; 0 disables buy_tokens
+    pub tokens_per_sol: u64,
+    pub min_purchase_lamports: u64,
+    // SPL token accepted by buy_tokens_with_spl, and whole tokens sold per whole payment token
+    pub payment_mint: Pubkey,
+    pub tokens_per_payment_token: u64,
+    // Backend key allowed to sign mint_with_voucher vouchers; default disables them
+    pub voucher_signer: Pubkey,
+    // Sliding-window limiter: at most max_mints_per_window mints per window_seconds;
+    // 0 mints disables it
+    pub window_seconds: i64,
+    pub max_mints_per_window: u8,
+    // Anti-sybil gating for mint_tokens; 0 / default disables each check
+    pub min_sol_balance: u64,
+    pub gate_mint: Pubkey,
+    // Amount claim_drip mints per wallet per day; 0 disables it
+    pub drip_amount: u64,
+    pub bump: u8,
+    // Layout version; new fields must be appended below this one so migrate_config
+    // can grow older accounts in place
+    pub version: u8,
+}
+
+impl Config {
+    // Defaults for a freshly created mint: no faucet limits beyond the supply cap,
+    // unpaused, and every optional feature switched off
+    pub fn init(&mut self, admin: Pubkey, mint: Pubkey, decimals: u8, max_supply: u64, bump: u8) {
+        self.admin = admin;
+        self.mint = mint;
+        self.decimals = decimals;
+        self.max_supply = max_supply;
+        self.reserved = 0;
+        self.max_per_wallet = max_supply;
+        self.max_per_tx = max_supply;
+        self.paused = false;
+        self.allowlist_only = false;
+        self.referral_bonus_bps = 0;
+        self.mint_start_ts = 0;
+        self.mint_end_ts = 0;
+        self.airdrop_root = [0u8; 32];
+        self.tokens_per_sol = 0;
+        self.min_purchase_lamports = 0;
+        self.payment_mint = Pubkey::default();
+        self.tokens_per_payment_token = 0;
+        self.voucher_signer = Pubkey::default();
+        self.window_seconds = 0;
+        self.max_mints_per_window = 0;
+        self.min_sol_balance = 0;
+        self.gate_mint = Pubkey::default();
+        self.drip_amount = 0;
+        self.bump = bump;
+        self.version = CONFIG_VERSION;
+    }
+
+    // Base units sold for `lamports` at the configured rate, checked against the
+    // minimum purchase and the supply cap given the mint's current supply
+    pub fn quote_lamports(&self, lamports: u64, current_supply: u64) -> Result<u64> {
+        require!(self.tokens_per_sol > 0, TokenDemoError::SaleNotConfigured);
+        require!(
+            lamports >= self.min_purchase_lamports,
+            TokenDemoError::BelowMinimumPurchase
+        );
+
+        // base units = lamports * tokens_per_sol * 10^decimals / LAMPORTS_PER_SOL,
+        // in u128 to avoid overflow
+        let token_amount = (lamports as u128)
+            .checked_mul(self.tokens_per_sol as u128)
+            .and_then(|value| value.checked_mul(10u128.pow(self.decimals as u32)))
+            .and_then(|value| value.checked_div(LAMPORTS_PER_SOL as u128))
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(TokenDemoError::MathOverflow)?;
+        require!(token_amount > 0, TokenDemoError::BelowMinimumPurchase);
+
+        let new_supply = current_supply
+            .checked_add(token_amount)
+            .ok_or(TokenDemoError::SupplyCapExceeded)?;
+        require!(new_supply <= self.max_supply, TokenDemoError::SupplyCapExceeded);
+
+        Ok(token_amount)
+    }
+}
+
+// Tokens sold through buy_tokens and not yet redeemed; the treasury backs only these
+#[account]
+#[derive(InitSpace)]
+pub struct SaleStats {
+    pub tokens_sold: u64,
+}
+
+// A buyer's tokens from buy_tokens that they can still redeem
+#[account]
+#[derive(InitSpace)]
+pub struct PurchaseRecord {
+    pub buyer: Pubkey,
+    pub purchased: u64,
+}
+
+// Marker PDA allowing a wallet to mint while allowlist_only is set
+#[account]
+#[derive(InitSpace)]
+pub struct AllowlistEntry {
+    pub wallet: Pubkey,
+}
+
+// Referral totals per (mint, referrer)
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralStats {
+    pub referrer: Pubkey,
+    pub referrals: u64,
+    pub total_bonus: u64,
+}
+
+// Linear vesting of tokens held in a vault owned by this PDA
+#[account]
+#[derive(InitSpace)]
+pub struct VestingSchedule {
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_ts: i64,
+    pub duration: i64,
+    pub bump: u8,
+}
+
+// Tokens held in a vault owned by this PDA until unlock_ts
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub unlock_ts: i64,
+    pub bump: u8,
+}
+
+// Marker proving a wallet has claimed its allocation for an airdrop root
+#[account]
+#[derive(InitSpace)]
+pub struct AirdropClaim {
+    pub claimer: Pubkey,
+    pub amount: u64,
+    pub claimed_at: i64,
+}
+
+// Per-mint statistics, updated by mint_tokens
+#[account]
+#[derive(InitSpace)]
+pub struct MintStats {
+    pub total_minted: u64,
+    pub total_mints: u64,
+    pub unique_recipients: u64,
+}
+
+// Marker proving a voucher signature has been redeemed
+#[account]
+#[derive(InitSpace)]
+pub struct VoucherRedemption {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub redeemed_at: i64,
+}
+
+// Audit record of a single mint_tokens call
+#[account]
+#[derive(InitSpace)]
+pub struct MintReceipt {
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+// A holder's balance and the mint's supply at a given slot
+#[account]
+#[derive(InitSpace)]
+pub struct BalanceSnapshot {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub balance: u64,
+    pub supply: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+// Per-wallet faucet usage. Fields are only ever appended; records created before
+// one was added are grown with migrate_mint_record
+#[account]
+#[derive(InitSpace)]
+pub struct MintRecord {
+    pub last_mint_ts: i64,
+    pub total_minted: u64,
+    // Timestamps of the latest mints, used by the sliding-window limiter
+    pub recent_mints: [i64; MAX_WINDOW_MINTS],
+    // Last claim_drip timestamp; 0 until the first drip
+    pub last_drip_ts: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, decimals: u8)]
+pub struct CreateMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    // One config per mint
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MintStats::INIT_SPACE,
+        seeds = [b"mint_stats", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_stats: Account<'info, MintStats>,
+
+    // Token mint PDA derived from its name, so every team can have its own
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"mint", name.as_bytes()],
+        bump,
+        mint::decimals = decimals,
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
+        mint::token_program = token_program,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, decimals: u8)]
+pub struct CreateClawbackMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MintStats::INIT_SPACE,
+        seeds = [b"mint_stats", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_stats: Account<'info, MintStats>,
+
+    // Token-2022 mint with the PermanentDelegate extension
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"mint", name.as_bytes()],
+        bump,
+        mint::decimals = decimals,
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
+        mint::token_program = token_program,
+        extensions::permanent_delegate::delegate = mint_authority,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // Mint authority PDA, also the permanent delegate
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct Clawback<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(address = config.mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Mint authority PDA, acting as permanent delegate
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreateSoulboundMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MintStats::INIT_SPACE,
+        seeds = [b"mint_stats", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_stats: Account<'info, MintStats>,
+
+    // Created and initialized inside the instruction
+    /// CHECK: Uninitialized mint PDA; created with the NonTransferable extension
+    #[account(
+        mut,
+        seeds = [b"mint", name.as_bytes()],
+        bump,
+    )]
+    pub token_mint: UncheckedAccount<'info>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreateTransferHookMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MintStats::INIT_SPACE,
+        seeds = [b"mint_stats", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_stats: Account<'info, MintStats>,
+
+    // Created and initialized inside the instruction
+    /// CHECK: Uninitialized mint PDA; created with the TransferHook extension
+    #[account(
+        mut,
+        seeds = [b"mint", name.as_bytes()],
+        bump,
+    )]
+    pub token_mint: UncheckedAccount<'info>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreateInterestBearingMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MintStats::INIT_SPACE,
+        seeds = [b"mint_stats", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_stats: Account<'info, MintStats>,
+
+    // Created and initialized inside the instruction
+    /// CHECK: Uninitialized mint PDA; created with the InterestBearingConfig extension
+    #[account(
+        mut,
+        seeds = [b"mint", name.as_bytes()],
+        bump,
+    )]
+    pub token_mint: UncheckedAccount<'info>,
+
+    // Mint authority PDA, also the rate authority
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRate<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // Mint authority PDA, also the rate authority
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, decimals: u8)]
+pub struct CreateMintWithMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MintStats::INIT_SPACE,
+        seeds = [b"mint_stats", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_stats: Account<'info, MintStats>,
+
+    // Token-2022 mint whose metadata pointer points at itself
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"mint", name.as_bytes()],
+        bump,
+        mint::decimals = decimals,
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
+        mint::token_program = token_program,
+        extensions::metadata_pointer::authority = mint_authority,
+        extensions::metadata_pointer::metadata_address = token_mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // Mint authority PDA, also the metadata update authority
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // Mint authority PDA, also the metadata update authority
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(address = config.mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // Metadata account derived by the Token Metadata program
+    /// CHECK: Created and validated by the Token Metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct ConfigureTransferFee<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MintStats::INIT_SPACE,
+        seeds = [b"mint_stats", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_stats: Account<'info, MintStats>,
+
+    // Created and initialized inside the instruction
+    /// CHECK: Uninitialized mint PDA; created with the TransferFeeConfig extension
+    #[account(
+        mut,
+        seeds = [b"mint", name.as_bytes()],
+        bump,
+    )]
+    pub token_mint: UncheckedAccount<'info>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct HarvestFees<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    // Transfer-fee mint created by configure_transfer_fee
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // Program-owned token account collecting this mint's withheld fees
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"fee_vault", token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = mint_authority,
+        token::token_program = token_program,
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(address = config.mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // Any token account for the mint
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    // Mint authority PDA, which owns the fee vault
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct FreezeOrThaw<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(address = config.mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // Any holder's token account for this mint
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Mint authority PDA, also the freeze authority
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint and freeze authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeMintAuthority<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RotateToMultisig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Unpacked as an SPL Token Multisig in the instruction
+    #[account(owner = token_program.key() @ TokenDemoError::InvalidMultisig)]
+    pub multisig: UncheckedAccount<'info>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct MintWithMultisig<'info> {
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_stats", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_stats: Account<'info, MintStats>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Must be the mint's current mint authority, checked in the instruction
+    #[account(owner = token_program.key() @ TokenDemoError::InvalidMultisig)]
+    pub multisig: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateMintRecord<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Only used as a seed; any wallet's record may be migrated
+    pub user: UncheckedAccount<'info>,
+
+    // Unchecked because an outdated layout cannot be deserialized as MintRecord
+    /// CHECK: Seeds and owner are checked here, layout in the instruction
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"mint_record", token_mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub mint_record: UncheckedAccount<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    // Unchecked because an outdated layout cannot be deserialized as Config
+    /// CHECK: Seeds and owner are checked here, admin and layout in the instruction
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"config", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AddToAllowlist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AllowlistEntry::INIT_SPACE,
+        seeds = [b"allowlist", config.mint.as_ref(), wallet.as_ref()],
+        bump,
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveFromAllowlist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"allowlist", config.mint.as_ref(), allowlist_entry.wallet.as_ref()],
+        bump,
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+}
+
+#[derive(Accounts)]
+pub struct MintTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    // Token mint created by create_mint
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    // User's token account - created in the instruction with create_idempotent,
+    // which costs less compute than init_if_needed and needs no rent sysvar
+    /// CHECK: Address is the user's ATA; the token program validates it on mint
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &user.key(),
+            &token_mint.key(),
+            &token_program.key(),
+        ) @ TokenDemoError::InvalidRecipientAccount,
+    )]
+    pub user_token_account: UncheckedAccount<'info>,
+
+    // User's faucet usage - created on their first mint
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + MintRecord::INIT_SPACE,
+        seeds = [b"mint_record", token_mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub mint_record: Account<'info, MintRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_stats", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_stats: Account<'info, MintStats>,
+
+    // One receipt per call, seeded by the mint's running total_mints nonce
+    #[account(
+        init,
+        payer = user,
+        space = 8 + MintReceipt::INIT_SPACE,
+        seeds = [
+            b"receipt",
+            token_mint.key().as_ref(),
+            user.key().as_ref(),
+            mint_stats.total_mints.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+
+    // Only required while config.allowlist_only is enabled
+    #[account(
+        seeds = [b"allowlist", token_mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+
+    // Only required while config.gate_mint is set; any token account the user owns
+    #[account(token::authority = user)]
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // Optional referrer; when present, the three accounts below are required too
+    /// CHECK: Any wallet except the user; only used as ATA owner and stats key
+    pub referrer: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = referrer,
+        token::token_program = token_program,
+    )]
+    pub referrer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + ReferralStats::INIT_SPACE,
+        seeds = [b"referral", token_mint.key().as_ref(), referrer.key().as_ref()],
+        bump,
+    )]
+    pub referral_stats: Option<Account<'info, ReferralStats>>,
+
+    // Referrer's faucet usage; the bonus counts towards their per-wallet cap
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + MintRecord::INIT_SPACE,
+        seeds = [b"mint_record", token_mint.key().as_ref(), referrer.key().as_ref()],
+        bump,
+    )]
+    pub referrer_record: Option<Account<'info, MintRecord>>,
+    
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+    
+    // Only required when a memo is passed
+    pub memo_program: Option<Program<'info, Memo>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDrip<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // User's token account - created in the instruction if it doesn't exist
+    /// CHECK: Address is the user's ATA; the token program validates it on mint
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &user.key(),
+            &token_mint.key(),
+            &token_program.key(),
+        ) @ TokenDemoError::InvalidRecipientAccount,
+    )]
+    pub user_token_account: UncheckedAccount<'info>,
+
+    // Shared with mint_tokens; tracks the last drip alongside faucet usage
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + MintRecord::INIT_SPACE,
+        seeds = [b"mint_record", token_mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub mint_record: Account<'info, MintRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_stats", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_stats: Account<'info, MintStats>,
+
+    // Only required while config.allowlist_only is enabled
+    #[account(
+        seeds = [b"allowlist", token_mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+
+    // Only required while config.gate_mint is set; any token account the user owns
+    #[account(token::authority = user)]
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct BatchMint<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAirdrop<'info> {
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // Claimer's token account - created in the instruction if it doesn't exist
+    /// CHECK: Address is the claimer's ATA; the token program validates it on mint
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &claimer.key(),
+            &token_mint.key(),
+            &token_program.key(),
+        ) @ TokenDemoError::InvalidRecipientAccount,
+    )]
+    pub claimer_token_account: UncheckedAccount<'info>,
+
+    // One marker per (root, claimer); a new root opens a new round
+    #[account(
+        init,
+        payer = claimer,
+        space = 8 + AirdropClaim::INIT_SPACE,
+        seeds = [
+            b"airdrop_claim",
+            token_mint.key().as_ref(),
+            config.airdrop_root.as_ref(),
+            claimer.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub claim_marker: Account<'info, AirdropClaim>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, expiry: i64, signature: [u8; 64])]
+pub struct MintWithVoucher<'info> {
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // Recipient's token account - created in the instruction if it doesn't exist
+    /// CHECK: Address is the recipient's ATA; the token program validates it on mint
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &recipient.key(),
+            &token_mint.key(),
+            &token_program.key(),
+        ) @ TokenDemoError::InvalidRecipientAccount,
+    )]
+    pub recipient_token_account: UncheckedAccount<'info>,
+
+    // One marker per signature; seeds are capped at 32 bytes, so it is split in two
+    #[account(
+        init,
+        payer = recipient,
+        space = 8 + VoucherRedemption::INIT_SPACE,
+        seeds = [b"voucher", signature[..32].as_ref(), signature[32..].as_ref()],
+        bump,
+    )]
+    pub voucher_redemption: Account<'info, VoucherRedemption>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    /// CHECK: The instructions sysvar, read to find the ed25519 verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct Quote<'info> {
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(address = config.mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct GetStats<'info> {
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(address = config.mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"mint_stats", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_stats: Account<'info, MintStats>,
+}
+
+#[derive(Accounts)]
+pub struct BuyTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // User's token account - created in the instruction if it doesn't exist
+    /// CHECK: Address is the user's ATA; the token program validates it on mint
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &user.key(),
+            &token_mint.key(),
+            &token_program.key(),
+        ) @ TokenDemoError::InvalidRecipientAccount,
+    )]
+    pub user_token_account: UncheckedAccount<'info>,
+
+    // PDA collecting sale proceeds
+    #[account(
+        mut,
+        seeds = [b"treasury", config.mint.as_ref()],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    // Tokens the treasury backs - created on the first purchase
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + SaleStats::INIT_SPACE,
+        seeds = [b"sale_stats", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub sale_stats: Account<'info, SaleStats>,
+
+    // The user's redeemable purchases - created on their first purchase
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + PurchaseRecord::INIT_SPACE,
+        seeds = [b"purchase", token_mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub purchase_record: Account<'info, PurchaseRecord>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaymentMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    // Program-owned account receiving payments, owned by the treasury PDA
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"payment_vault", config.mint.as_ref(), payment_mint.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = treasury,
+        token::token_program = payment_token_program,
+    )]
+    pub payment_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"treasury", config.mint.as_ref()],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub payment_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct BuyTokensWithSpl<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(address = config.payment_mint)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    // User's payment token account (e.g. their USDC ATA)
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = user,
+        token::token_program = payment_token_program,
+    )]
+    pub user_payment_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"payment_vault", config.mint.as_ref(), payment_mint.key().as_ref()],
+        bump,
+    )]
+    pub payment_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // User's token account - created in the instruction if it doesn't exist
+    /// CHECK: Address is the user's ATA; the token program validates it on mint
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &user.key(),
+            &token_mint.key(),
+            &token_program.key(),
+        ) @ TokenDemoError::InvalidRecipientAccount,
+    )]
+    pub user_token_account: UncheckedAccount<'info>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub payment_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 #[derive(Accounts)]
-pub struct MintTokens<'info> {
+pub struct Redeem<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
-    
-    // Token mint using PDA for deterministic address
+
     #[account(
-        init_if_needed,
-        payer = user,
-        seeds = [b"spacex_token_mint"],
-        bump,
-        mint::decimals = 6,
-        mint::authority = mint_authority,
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
     )]
-    pub token_mint: Account<'info, Mint>,
-    
-    // User's token account - created automatically if it doesn't exist
+    pub config: Account<'info, Config>,
+
     #[account(
-        init_if_needed,
-        payer = user,
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
         associated_token::mint = token_mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // SOL backing the token, filled by buy_tokens
+    #[account(
+        mut,
+        seeds = [b"treasury", config.mint.as_ref()],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sale_stats", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub sale_stats: Account<'info, SaleStats>,
+
+    // Exists only for wallets that have bought with buy_tokens
+    #[account(
+        mut,
+        seeds = [b"purchase", token_mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub purchase_record: Account<'info, PurchaseRecord>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct MintVested<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Wallet that will receive the vested tokens
+    pub beneficiary: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + VestingSchedule::INIT_SPACE,
+        seeds = [b"vesting", token_mint.key().as_ref(), beneficiary.key().as_ref()],
+        bump,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    // Program-owned token account holding the locked tokens
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"vesting_vault", vesting_schedule.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = vesting_schedule,
+        token::token_program = token_program,
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
     // Mint authority PDA
     #[account(
         seeds = [b"mint_authority"],
@@ -79,21 +4262,446 @@ pub struct MintTokens<'info> {
     )]
     /// CHECK: This is a PDA used as the mint authority
     pub mint_authority: AccountInfo<'info>,
-    
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting", vesting_schedule.mint.as_ref(), beneficiary.key().as_ref()],
+        bump = vesting_schedule.bump,
+        has_one = beneficiary @ TokenDemoError::Unauthorized,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(address = vesting_schedule.mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting_vault", vesting_schedule.key().as_ref()],
+        bump,
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // Beneficiary's token account - created in the instruction if it doesn't exist
+    /// CHECK: Address is the beneficiary's ATA; the token program validates it on transfer
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &beneficiary.key(),
+            &token_mint.key(),
+            &token_program.key(),
+        ) @ TokenDemoError::InvalidRecipientAccount,
+    )]
+    pub beneficiary_token_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct MintToEscrow<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.mint.as_ref()],
+        bump = config.bump,
+        has_one = admin @ TokenDemoError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.mint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Wallet that can release the escrow once unlocked
+    pub beneficiary: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", token_mint.key().as_ref(), beneficiary.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // Program-owned token account holding the locked tokens
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"escrow_vault", escrow.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = escrow,
+        token::token_program = token_program,
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // Mint authority PDA
+    #[account(
+        seeds = [b"mint_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as the mint authority
+    pub mint_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseEscrow<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    // Closed after the release; its rent goes to the beneficiary
+    #[account(
+        mut,
+        close = beneficiary,
+        seeds = [b"escrow", escrow.mint.as_ref(), beneficiary.key().as_ref()],
+        bump = escrow.bump,
+        has_one = beneficiary @ TokenDemoError::Unauthorized,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(address = escrow.mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow_vault", escrow.key().as_ref()],
+        bump,
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // Beneficiary's token account - created in the instruction if it doesn't exist
+    /// CHECK: Address is the beneficiary's ATA; the token program validates it on transfer
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &beneficiary.key(),
+            &token_mint.key(),
+            &token_program.key(),
+        ) @ TokenDemoError::InvalidRecipientAccount,
+    )]
+    pub beneficiary_token_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct SnapshotBalance<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    // Works with any mint, including those from create_mint
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + BalanceSnapshot::INIT_SPACE,
+        seeds = [b"snapshot", token_mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub balance_snapshot: Account<'info, BalanceSnapshot>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CloseMintReceipt<'info> {
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        close = recipient,
+        has_one = recipient @ TokenDemoError::Unauthorized,
+        seeds = [
+            b"receipt",
+            mint_receipt.mint.as_ref(),
+            recipient.key().as_ref(),
+            mint_receipt.nonce.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+}
+
+#[derive(Accounts)]
+pub struct BurnTokens<'info> {
+    pub user: Signer<'info>,
+
+    // Works with any mint, including those from create_mint
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // User's token account to burn from
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CloseUserTokenAccount<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    // Works with any mint, including those from create_mint
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // User's token account to close; rent goes back to the user
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveDelegate<'info> {
+    pub user: Signer<'info>,
+
+    /// CHECK: Any wallet or PDA can be a delegate
+    pub delegate: AccountInfo<'info>,
+
+    // Works with any mint, including those from create_mint
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    pub user: Signer<'info>,
+
+    // Works with any mint, including those from create_mint
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct WrapSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(address = native_mint::ID)]
+    pub native_mint: InterfaceAccount<'info, Mint>,
+
+    // User's wSOL account - created in the instruction if it doesn't exist
+    /// CHECK: Address is the user's wSOL ATA; the token program validates it on sync
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &user.key(),
+            &native_mint.key(),
+            &token_program.key(),
+        ) @ TokenDemoError::InvalidRecipientAccount,
+    )]
+    pub wsol_account: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
-impl<'info> MintTokens<'info> {
-    // Helper property to check if mint is already initialized
-    pub fn is_mint_initialized(&self) -> bool {
-        self.token_mint.mint_authority.is_some()
-    }
-    
-    // Helper property to check if token account already exists
-    pub fn has_token_account(&self) -> bool {
-        self.user_token_account.owner == self.user.key()
-    }
-}
\ No newline at end of file
+#[derive(Accounts)]
+pub struct UnwrapSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(address = native_mint::ID)]
+    pub native_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = native_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub wsol_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TransferTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Any wallet can receive tokens; only used as the ATA owner
+    pub recipient: AccountInfo<'info>,
+
+    // Works with any mint, including those from create_mint
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    // Sender's token account
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Recipient's token account - created in the instruction if it doesn't exist
+    /// CHECK: Address is the recipient's ATA; the token program validates it on transfer
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &recipient.key(),
+            &token_mint.key(),
+            &token_program.key(),
+        ) @ TokenDemoError::InvalidRecipientAccount,
+    )]
+    pub recipient_token_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[event]
+pub struct TokenMinted {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub new_supply: u64,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum TokenDemoError {
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Only the admin can perform this action")]
+    Unauthorized,
+    #[msg("Mint name must be 1-32 bytes")]
+    InvalidMintName,
+    #[msg("Decimals must be at most 9")]
+    InvalidDecimals,
+    #[msg("This wallet is not on the allowlist")]
+    NotAllowlisted,
+    #[msg("Referrer is invalid or its accounts are missing")]
+    InvalidReferrer,
+    #[msg("Referral bonus must be at most 10000 basis points")]
+    InvalidReferralBonus,
+    #[msg("Token balance is too low for this operation")]
+    InsufficientBalance,
+    #[msg("Minting this amount would exceed the maximum supply")]
+    SupplyCapExceeded,
+    #[msg("Minting this amount would exceed the per-wallet limit")]
+    WalletCapExceeded,
+    #[msg("Minting is currently paused")]
+    MintingPaused,
+    #[msg("Minting has not started yet")]
+    MintNotStarted,
+    #[msg("Minting has ended")]
+    MintEnded,
+    #[msg("Mint window start must be before its end")]
+    InvalidMintWindow,
+    #[msg("Amount exceeds the per-transaction mint limit")]
+    ExceedsPerTxLimit,
+    #[msg("Fee basis points must be at most 10000")]
+    InvalidFee,
+    #[msg("The mint authority has been revoked; supply is fixed")]
+    SupplyFinalized,
+    #[msg("Number of amounts must match the number of recipient accounts")]
+    BatchLengthMismatch,
+    #[msg("Recipient account is not an associated token account for this mint")]
+    InvalidRecipientAccount,
+    #[msg("Merkle proof does not match the airdrop root")]
+    InvalidMerkleProof,
+    #[msg("The token sale has not been configured")]
+    SaleNotConfigured,
+    #[msg("Purchase is below the minimum amount")]
+    BelowMinimumPurchase,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("The treasury has no SOL to redeem for this amount")]
+    NothingToRedeem,
+    #[msg("Only tokens bought with buy_tokens can be redeemed")]
+    NotPurchased,
+    #[msg("Vesting duration must be positive")]
+    InvalidVestingDuration,
+    #[msg("Nothing is available to claim yet")]
+    NothingToClaim,
+    #[msg("Voucher signature is missing or invalid")]
+    InvalidVoucher,
+    #[msg("Voucher has expired")]
+    VoucherExpired,
+    #[msg("Rate limit window must be positive and allow at most 10 mints")]
+    InvalidRateLimit,
+    #[msg("This wallet has minted too many times in the current window")]
+    RateLimited,
+    #[msg("Wallet does not meet the minimum SOL or gate token requirement")]
+    SybilCheckFailed,
+    #[msg("The daily drip has not been configured")]
+    DripNotConfigured,
+    #[msg("This wallet has already claimed its drip in the last 24 hours")]
+    DripNotReady,
+    #[msg("Account is not the mint's SPL Token multisig, or a signer is missing")]
+    InvalidMultisig,
+    #[msg("Unlock time must be in the future")]
+    InvalidUnlockTime,
+    #[msg("This escrow is still locked")]
+    EscrowLocked,
+    #[msg("Account is not a config created by this program")]
+    InvalidConfigAccount,
+    #[msg("Account is not an outdated mint record created by this program")]
+    InvalidMintRecordAccount,
+    #[msg("Memo must be at most 256 bytes")]
+    MemoTooLong,
+    #[msg("The Memo program account is required when a memo is passed")]
+    MissingMemoProgram,
+}